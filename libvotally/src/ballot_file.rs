@@ -0,0 +1,127 @@
+//! Readers and writers of election interchange formats,
+//! to check counts against other tabulators and load existing data sets
+
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::voting_system::{RawBallots, SingleBallot};
+
+mod blt;
+pub use self::blt::{read_blt, write_blt};
+
+mod preflib;
+pub use self::preflib::{read_preflib, write_preflib};
+
+mod abif;
+pub use self::abif::{read_abif, write_abif};
+
+mod csv;
+pub use self::csv::{read_csv, write_csv};
+
+/// Interchange format of a ballot file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BallotFileFormat {
+    /// OpenSTV ballot file
+    Blt,
+    /// PrefLib orders, `.soc`, `.soi`, `.toc` or `.toi` file
+    PrefLib,
+    /// Aggregated Ballot Information Format
+    Abif,
+    /// Ballot sheet with one column per candidate and one row per ballot
+    Csv,
+}
+
+impl BallotFileFormat {
+    /// Find the format of a file from its extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+
+    /// Read ballots written in this format
+    pub fn read(&self, input: &str) -> Result<RawBallots, InvalidBallotFile> {
+        match self {
+            BallotFileFormat::Blt => read_blt(input),
+            BallotFileFormat::PrefLib => read_preflib(input),
+            BallotFileFormat::Abif => read_abif(input),
+            BallotFileFormat::Csv => read_csv(input),
+        }
+    }
+
+    /// Write ballots in this format
+    pub fn write(&self, ballots: &RawBallots) -> Result<String, InvalidBallotFile> {
+        match self {
+            BallotFileFormat::Blt => write_blt(ballots),
+            BallotFileFormat::PrefLib => write_preflib(ballots),
+            BallotFileFormat::Abif => write_abif(ballots),
+            BallotFileFormat::Csv => write_csv(ballots),
+        }
+    }
+}
+
+impl FromStr for BallotFileFormat {
+    type Err = InvalidBallotFile;
+
+    /// Parse the name or the file extension of a format
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.to_lowercase()[..] {
+            "blt" => Ok(BallotFileFormat::Blt),
+            "preflib" | "soc" | "soi" | "toc" | "toi" => Ok(BallotFileFormat::PrefLib),
+            "abif" => Ok(BallotFileFormat::Abif),
+            "csv" => Ok(BallotFileFormat::Csv),
+            _ => Err(InvalidBallotFile(format!("unknown format {}", s))),
+        }
+    }
+}
+
+/// Get the groups of equally ranked choices of a uninominal or ranked ballot
+fn ranking(ballot: &SingleBallot) -> Option<Vec<Vec<String>>> {
+    match ballot {
+        SingleBallot::Uninominal(c) => Some(vec![vec![c.to_owned()]]),
+        SingleBallot::Ranked(ranked) => Some(ranked.iter().map(|c| vec![c.to_owned()]).collect()),
+        SingleBallot::RankedWithTies(groups) => Some(groups.clone()),
+        _ => None,
+    }
+}
+
+/// Create a ranked ballot from groups of equally ranked choices
+fn ranked_ballot(groups: Vec<Vec<String>>) -> SingleBallot {
+    if groups.iter().all(|group| group.len() == 1) {
+        SingleBallot::Ranked(groups.into_iter().flatten().collect())
+    } else {
+        SingleBallot::RankedWithTies(groups)
+    }
+}
+
+/// Error for invalid ballot file
+#[derive(Debug)]
+pub struct InvalidBallotFile(String);
+
+impl InvalidBallotFile {
+    /// Error found on a line of the file
+    fn at_line(line: usize, message: &str) -> Self {
+        Self(format!("line {}: {}", line, message))
+    }
+
+    /// Ballot which can't be written in a format
+    fn unsupported(ballot: &SingleBallot, format: &str) -> Self {
+        Self(format!("{:?} can't be written in {}", ballot, format))
+    }
+
+    /// Candidate name which the format can't hold
+    fn unsupported_name(name: &str, format: &str) -> Self {
+        Self(format!(
+            "the name {:?} can't be written in {}",
+            name, format
+        ))
+    }
+}
+
+impl fmt::Display for InvalidBallotFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid ballot file: {}", self.0)
+    }
+}
+
+impl Error for InvalidBallotFile {}
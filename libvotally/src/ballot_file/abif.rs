@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+
+use crate::ballot_file::{InvalidBallotFile, ranked_ballot, ranking};
+use crate::voting_system::{RawBallots, SingleBallot, WeightedBallot};
+
+/// One candidate of an ABIF ballot with its rating, if any
+type Rated = (String, Option<u8>);
+
+/// Read an Aggregated Ballot Information Format file.
+/// Ballots where every candidate is rated, like `A/5>B/2`, become scored ballots,
+/// other ballots become ranked ballots.
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::ballot_file::read_abif;
+///
+/// let ballots = read_abif(
+///     "{\"title\": \"Treasurer\"}
+///     =A:[Alice]
+///     =B:[Bob]
+///     24:A>B
+///     10:B=A",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(vec!["Alice", "Bob"], ballots.get_choices());
+/// assert_eq!(24, ballots.get_ballots()[0].get_weight());
+/// ```
+pub fn read_abif(input: &str) -> Result<RawBallots, InvalidBallotFile> {
+    let mut tokens: HashMap<String, String> = HashMap::new();
+    let mut choices: Vec<String> = Vec::new();
+    let mut ballots: Vec<WeightedBallot> = Vec::new();
+
+    for (n, line) in input.lines().enumerate().map(|(n, l)| (n + 1, l.trim())) {
+        let line = strip_comment(line).trim();
+        // Metadata written in JSON
+        if line.is_empty() || line.starts_with('{') {
+            continue;
+        }
+
+        // Candidate declaration, like =A:[Alice]
+        if let Some(declaration) = line.strip_prefix('=') {
+            let (token, name) = match split_declaration(declaration) {
+                Some((token, name)) => (unquote(token.trim()), unquote(name.trim())),
+                None => (unquote(declaration.trim()), unquote(declaration.trim())),
+            };
+            if name.is_empty() || choices.contains(&name) {
+                return Err(InvalidBallotFile::at_line(
+                    n,
+                    "invalid candidate declaration",
+                ));
+            }
+            tokens.insert(token, name.clone());
+            choices.push(name);
+            continue;
+        }
+
+        let (count, ranking) = line
+            .split_once(':')
+            .ok_or(InvalidBallotFile::at_line(n, "expected <count>:<ranking>"))?;
+        let count: u32 = count
+            .trim()
+            .parse()
+            .map_err(|_| InvalidBallotFile::at_line(n, "invalid count"))?;
+
+        let groups: Vec<Vec<Rated>> =
+            parse_ranking(ranking).map_err(|e| InvalidBallotFile::at_line(n, &e))?;
+        let groups: Vec<Vec<Rated>> = groups
+            .into_iter()
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|(c, rating)| (tokens.get(&c).cloned().unwrap_or(c), rating))
+                    .collect()
+            })
+            .collect();
+
+        // Candidates which were not declared are added in the order they appear
+        for (c, _) in groups.iter().flatten() {
+            if !choices.contains(c) {
+                choices.push(c.to_owned());
+            }
+        }
+
+        let rated = groups.iter().flatten().all(|(_, rating)| rating.is_some());
+        let ballot = if rated && !groups.is_empty() {
+            SingleBallot::Scored(
+                groups
+                    .into_iter()
+                    .flatten()
+                    .map(|(c, rating)| (c, rating.unwrap()))
+                    .collect(),
+            )
+        } else {
+            ranked_ballot(
+                groups
+                    .into_iter()
+                    .map(|group| group.into_iter().map(|(c, _)| c).collect())
+                    .collect(),
+            )
+        };
+        ballots.push(WeightedBallot::new(ballot, count));
+    }
+
+    let choices: Vec<&str> = choices.iter().map(|c| c.as_str()).collect();
+    Ok(RawBallots::from_weighted_ballots(&choices, ballots))
+}
+
+/// Remove a comment starting with # outside of brackets
+fn strip_comment(line: &str) -> &str {
+    let mut bracket = false;
+    for (i, ch) in line.char_indices() {
+        match ch {
+            '[' => bracket = true,
+            ']' => bracket = false,
+            '#' if !bracket => return &line[..i],
+            _ => (),
+        }
+    }
+    line
+}
+
+/// Split a candidate declaration at the colon which isn't in a name
+fn split_declaration(declaration: &str) -> Option<(&str, &str)> {
+    let mut bracket = false;
+    for (i, ch) in declaration.char_indices() {
+        match ch {
+            '[' => bracket = true,
+            ']' => bracket = false,
+            ':' if !bracket => return Some((&declaration[..i], &declaration[i + 1..])),
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Remove the brackets around a candidate name
+fn unquote(token: &str) -> String {
+    token
+        .strip_prefix('[')
+        .and_then(|t| t.strip_suffix(']'))
+        .unwrap_or(token)
+        .to_owned()
+}
+
+/// Parse a ranking like `A/5>[Bob Smith]=C`, `>` separating ranks
+/// and `=` or `,` separating equally ranked candidates
+fn parse_ranking(ranking: &str) -> Result<Vec<Vec<Rated>>, String> {
+    let mut groups: Vec<Vec<Rated>> = vec![Vec::new()];
+    let mut token = String::new();
+    let mut bracket = false;
+
+    for ch in ranking.chars() {
+        match ch {
+            '[' if !bracket => {
+                bracket = true;
+                token.push(ch);
+            }
+            ']' if bracket => {
+                bracket = false;
+                token.push(ch);
+            }
+            '>' if !bracket => {
+                end_token(&mut token, &mut groups)?;
+                groups.push(Vec::new());
+            }
+            '=' | ',' if !bracket => end_token(&mut token, &mut groups)?,
+            _ => token.push(ch),
+        }
+    }
+    if bracket {
+        return Err("unclosed bracket".to_string());
+    }
+    end_token(&mut token, &mut groups)?;
+
+    if groups.len() > 1 && groups.iter().any(|group| group.is_empty()) {
+        return Err("empty rank".to_string());
+    }
+
+    Ok(groups
+        .into_iter()
+        .filter(|group| !group.is_empty())
+        .collect())
+}
+
+/// Add the candidate being read to the last rank
+fn end_token(token: &mut String, groups: &mut [Vec<Rated>]) -> Result<(), String> {
+    let t = token.trim();
+    if !t.is_empty() {
+        let (name, rating) = match t.rsplit_once('/') {
+            Some((name, rating)) if !rating.ends_with(']') => (
+                name.trim(),
+                Some(
+                    rating
+                        .trim()
+                        .parse::<u8>()
+                        .map_err(|_| format!("invalid rating {}", rating))?,
+                ),
+            ),
+            _ => (t, None),
+        };
+        groups.last_mut().unwrap().push((unquote(name), rating));
+    }
+    token.clear();
+    Ok(())
+}
+
+/// Write a candidate as a bare token if possible and between brackets else
+fn token(c: &str) -> String {
+    if !c.is_empty()
+        && c.chars()
+            .all(|ch| ch.is_alphanumeric() || ch == '_' || ch == '-')
+    {
+        c.to_owned()
+    } else {
+        format!("[{}]", c)
+    }
+}
+
+/// Write uninominal, ranked and scored ballots in an Aggregated Ballot Information Format file.
+/// Names can't hold closing brackets or line breaks, which the format can't escape.
+pub fn write_abif(ballots: &RawBallots) -> Result<String, InvalidBallotFile> {
+    let choices = ballots.get_choices();
+    if let Some(c) = choices.iter().find(|c| c.contains([']', '\n', '\r'])) {
+        return Err(InvalidBallotFile::unsupported_name(c, "ABIF"));
+    }
+
+    let mut output = String::new();
+    for c in ballots.get_choices() {
+        output += &format!("={}:[{}]\n", token(c), c);
+    }
+
+    for b in ballots.get_ballots() {
+        let ranking = match b.get_ballot() {
+            SingleBallot::Scored(scores) => {
+                // Higher scores first, each candidate keeping its rating
+                let mut sorted: Vec<(&String, &u8)> = scores.iter().collect();
+                sorted.sort_by(|(c1, s1), (c2, s2)| s2.cmp(s1).then(c1.cmp(c2)));
+                let mut ranking = String::new();
+                for (i, (c, s)) in sorted.iter().enumerate() {
+                    if i > 0 {
+                        ranking += if sorted[i - 1].1 == *s { "=" } else { ">" };
+                    }
+                    ranking += &format!("{}/{}", token(c), s);
+                }
+                ranking
+            }
+            ballot => ranking(ballot)
+                .ok_or(InvalidBallotFile::unsupported(ballot, "ABIF"))?
+                .iter()
+                .map(|group| {
+                    group
+                        .iter()
+                        .map(|c| token(c))
+                        .collect::<Vec<String>>()
+                        .join("=")
+                })
+                .collect::<Vec<String>>()
+                .join(">"),
+        };
+        output += &format!("{}:{}\n", b.get_weight(), ranking);
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn abif_file() {
+        let input = "# Board election
+            {\"title\": \"Board\"}
+            =A:[Alice Smith]
+            =B:[Bob]
+            12:A>B>[Carol #2]
+            5:B=A # equal
+            3:A/5,B/3,[Carol #2]/3";
+        let ballots = read_abif(input).unwrap();
+
+        assert_eq!(
+            vec!["Alice Smith", "Bob", "Carol #2"],
+            ballots.get_choices()
+        );
+        assert!(matches!(
+            ballots.get_ballots()[1].get_ballot(),
+            SingleBallot::RankedWithTies(groups) if groups.len() == 1
+        ));
+        assert!(matches!(
+            ballots.get_ballots()[2].get_ballot(),
+            SingleBallot::Scored(scores) if scores["Alice Smith"] == 5
+        ));
+
+        let written = write_abif(&ballots).unwrap();
+        assert!(written.contains("12:[Alice Smith]>Bob>[Carol #2]\n"));
+        assert!(written.contains("3:[Alice Smith]/5>Bob/3=[Carol #2]/3\n"));
+        assert_eq!(written, write_abif(&read_abif(&written).unwrap()).unwrap());
+
+        assert!(read_abif("2:A>>B").is_err());
+        assert!(read_abif("A>B").is_err());
+    }
+
+    #[test]
+    fn abif_names() {
+        let names = ["A/5", "B>C", "D:E=F, G #2"];
+        let ranked = SingleBallot::Ranked(names.iter().map(|c| c.to_string()).collect());
+        let ballots = RawBallots::from_ballots(&names, vec![ranked.clone()]);
+
+        let read = read_abif(&write_abif(&ballots).unwrap()).unwrap();
+        assert_eq!(ballots.get_choices(), read.get_choices());
+        assert!(matches!(
+            read.get_ballots()[0].get_ballot(),
+            SingleBallot::Ranked(r) if r.len() == 3
+        ));
+
+        // A closing bracket would end the name early
+        let ballots = RawBallots::from_ballots(&["[A]", "B]"], Vec::new());
+        assert!(write_abif(&ballots).is_err());
+    }
+}
@@ -0,0 +1,288 @@
+use crate::ballot_file::{InvalidBallotFile, ranked_ballot, ranking};
+use crate::voting_system::{RawBallots, WeightedBallot};
+
+/// Read an OpenSTV ballot file.
+/// Withdrawn candidates are removed from the choices and the ballots,
+/// the number of seats and the title are ignored.
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::ballot_file::read_blt;
+///
+/// let ballots = read_blt(
+///     "3 1
+///     4 1 2 0
+///     2 3 1=2 0
+///     0
+///     \"Alice\"
+///     \"Bob\"
+///     \"Carol\"
+///     \"Treasurer\"",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(vec!["Alice", "Bob", "Carol"], ballots.get_choices());
+/// assert_eq!(4, ballots.get_ballots()[0].get_weight());
+/// ```
+pub fn read_blt(input: &str) -> Result<RawBallots, InvalidBallotFile> {
+    // Numbers of the lines with their content, without comments
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(n, l)| (n + 1, strip_comment(l).trim()))
+        .filter(|(_, l)| !l.is_empty());
+
+    let (n, header) = lines
+        .next()
+        .ok_or(InvalidBallotFile("the file is empty".to_string()))?;
+    let number: usize = header
+        .split_whitespace()
+        .next()
+        .and_then(|c| c.parse().ok())
+        .ok_or(InvalidBallotFile::at_line(
+            n,
+            "expected the number of candidates",
+        ))?;
+
+    let mut withdrawn: Vec<usize> = Vec::new();
+    let mut groups_by_ballot: Vec<(u32, Vec<Vec<usize>>)> = Vec::new();
+    let mut ended = false;
+
+    for (n, line) in lines.by_ref() {
+        let mut tokens = line.split_whitespace().peekable();
+
+        // Withdrawn candidates are written as negative numbers
+        if line.starts_with('-') {
+            for t in tokens {
+                match t.strip_prefix('-').and_then(|c| c.parse::<usize>().ok()) {
+                    Some(c) if 1 <= c && c <= number => withdrawn.push(c),
+                    _ => return Err(InvalidBallotFile::at_line(n, "invalid withdrawn candidate")),
+                }
+            }
+            continue;
+        }
+
+        if line == "0" {
+            ended = true;
+            break;
+        }
+
+        // Skip the optional identifier of the ballot
+        if tokens.peek().is_some_and(|t| t.starts_with('(')) {
+            tokens.next();
+        }
+        let weight: u32 = tokens
+            .next()
+            .and_then(|w| w.parse().ok())
+            .ok_or(InvalidBallotFile::at_line(n, "invalid weight"))?;
+
+        let mut groups = Vec::new();
+        let mut terminated = false;
+        for t in tokens {
+            if t == "0" {
+                terminated = true;
+                break;
+            }
+            // A skipped rank
+            if t == "-" {
+                continue;
+            }
+
+            let group = t
+                .split('=')
+                .map(|c| c.parse::<usize>().ok().filter(|c| 1 <= *c && *c <= number))
+                .collect::<Option<Vec<usize>>>()
+                .ok_or(InvalidBallotFile::at_line(
+                    n,
+                    &format!("invalid candidate {}", t),
+                ))?;
+            groups.push(group);
+        }
+        if !terminated {
+            return Err(InvalidBallotFile::at_line(n, "a ballot must end with 0"));
+        }
+
+        groups_by_ballot.push((weight, groups));
+    }
+
+    if !ended {
+        return Err(InvalidBallotFile(
+            "the ballots must end with a line containing 0".to_string(),
+        ));
+    }
+
+    // Names of the candidates, then the title, one per line
+    let mut names: Vec<String> = Vec::new();
+    for (n, line) in lines {
+        if names.len() > number {
+            return Err(InvalidBallotFile::at_line(
+                n,
+                "unexpected line after the title",
+            ));
+        }
+        let name = unquote(line).ok_or(InvalidBallotFile::at_line(n, "expected a quoted name"))?;
+        names.push(name.to_owned());
+    }
+    if names.len() < number {
+        return Err(InvalidBallotFile(format!(
+            "expected {} candidate names, found {}",
+            number,
+            names.len()
+        )));
+    }
+
+    let choices: Vec<&str> = (1..=number)
+        .filter(|c| !withdrawn.contains(c))
+        .map(|c| names[c - 1].as_str())
+        .collect();
+    let ballots = groups_by_ballot
+        .into_iter()
+        .map(|(weight, groups)| {
+            let groups: Vec<Vec<String>> = groups
+                .into_iter()
+                .map(|group| {
+                    group
+                        .into_iter()
+                        .filter(|c| !withdrawn.contains(c))
+                        .map(|c| names[c - 1].to_owned())
+                        .collect::<Vec<String>>()
+                })
+                .filter(|group| !group.is_empty())
+                .collect();
+            WeightedBallot::new(ranked_ballot(groups), weight)
+        })
+        .collect();
+
+    Ok(RawBallots::from_weighted_ballots(&choices, ballots))
+}
+
+/// Remove the comment of a line, starting with a `#` which isn't in a name
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Get the name written between the quotes of a line
+fn unquote(line: &str) -> Option<&str> {
+    line.strip_prefix('"')?
+        .strip_suffix('"')
+        .filter(|name| !name.contains('"'))
+}
+
+/// Write uninominal and ranked ballots in an OpenSTV ballot file.
+/// RawBallots holds neither the number of seats nor the title,
+/// so the file is written for one seat with the title "Votally election".
+/// Names can't hold quotes or line breaks, which the format can't escape.
+pub fn write_blt(ballots: &RawBallots) -> Result<String, InvalidBallotFile> {
+    let choices = ballots.get_choices();
+    if let Some(c) = choices.iter().find(|c| c.contains(['"', '\n', '\r'])) {
+        return Err(InvalidBallotFile::unsupported_name(c, "BLT"));
+    }
+    let index = |c: &String| {
+        choices
+            .iter()
+            .position(|x| x == c)
+            .map(|i| (i + 1).to_string())
+    };
+
+    let mut output = format!("{} 1\n", choices.len());
+    for b in ballots.get_ballots() {
+        let groups =
+            ranking(b.get_ballot()).ok_or(InvalidBallotFile::unsupported(b.get_ballot(), "BLT"))?;
+
+        output += &b.get_weight().to_string();
+        for group in groups {
+            let group = group
+                .iter()
+                .map(index)
+                .collect::<Option<Vec<String>>>()
+                .ok_or(InvalidBallotFile::unsupported(b.get_ballot(), "BLT"))?;
+            output += " ";
+            output += &group.join("=");
+        }
+        output += " 0\n";
+    }
+    output += "0\n";
+
+    for c in choices {
+        output += &format!("\"{}\"\n", c);
+    }
+    output += "\"Votally election\"\n";
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voting_system::SingleBallot;
+
+    #[test]
+    fn blt_file() {
+        let input = "# Treasurer election
+            4 1
+            -4
+            3 1 2 3 0
+            (b2) 2 2 1=3 0
+            1 3 - 4 0
+            0
+            \"A\"
+            \"B\"
+            \"C\"
+            \"D\"
+            \"Treasurer\"";
+        let ballots = read_blt(input).unwrap();
+
+        // D is withdrawn
+        assert_eq!(vec!["A", "B", "C"], ballots.get_choices());
+        assert_eq!(3, ballots.get_ballots().len());
+        assert!(matches!(
+            ballots.get_ballots()[1].get_ballot(),
+            SingleBallot::RankedWithTies(groups) if groups.len() == 2
+        ));
+        assert!(matches!(
+            ballots.get_ballots()[2].get_ballot(),
+            SingleBallot::Ranked(ranked) if ranked == &vec!["C".to_string()]
+        ));
+
+        // Written and read back
+        let written = write_blt(&ballots).unwrap();
+        assert!(written.starts_with("3 1\n3 1 2 3 0\n2 2 1=3 0\n1 3 0\n0\n"));
+        let read = read_blt(&written).unwrap();
+        assert_eq!(ballots.get_choices(), read.get_choices());
+        assert_eq!(written, write_blt(&read).unwrap());
+
+        assert!(read_blt("2 1\n1 1 3 0\n0\n\"A\"\n\"B\"\n\"T\"").is_err());
+
+        // Names can hold a #, and are written one per line
+        let names = read_blt("2 1\n1 1 2 0\n0\n\"A #1\" # first\n\"B\"\n\"T\"").unwrap();
+        assert_eq!(vec!["A #1", "B"], names.get_choices());
+        assert!(
+            read_blt("2 1\n1 1 2 0\n0\n\"A\" \"B\"\n\"T\"")
+                .unwrap_err()
+                .to_string()
+                .contains("line 4")
+        );
+        assert!(read_blt("2 1\n1 1 2\n0\n\"A\"\n\"B\"\n\"T\"").is_err());
+    }
+
+    #[test]
+    fn blt_names() {
+        let ranked = SingleBallot::Ranked(vec!["A #1".to_string(), "B = 2".to_string()]);
+        let ballots = RawBallots::from_ballots(&["A #1", "B = 2"], vec![ranked]);
+
+        let read = read_blt(&write_blt(&ballots).unwrap()).unwrap();
+        assert_eq!(ballots.get_choices(), read.get_choices());
+
+        // A quote would end the name early
+        let ballots = RawBallots::from_ballots(&["\"A\"", "B"], Vec::new());
+        assert!(write_blt(&ballots).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+use crate::ballot_file::{InvalidBallotFile, ranked_ballot, ranking};
+use crate::voting_system::{RawBallots, WeightedBallot};
+
+/// Read a ballot sheet with one column per candidate and one row per ballot,
+/// each cell holding the rank given to the candidate and left empty if it is unranked.
+/// A first column named `weight` gives the number of votes carried by each ballot.
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::ballot_file::read_csv;
+///
+/// let ballots = read_csv("weight,Alice,Bob,Carol\n3,1,2,\n1,2,1,1").unwrap();
+///
+/// assert_eq!(vec!["Alice", "Bob", "Carol"], ballots.get_choices());
+/// assert_eq!(3, ballots.get_ballots()[0].get_weight());
+/// ```
+pub fn read_csv(input: &str) -> Result<RawBallots, InvalidBallotFile> {
+    let mut rows = input
+        .lines()
+        .enumerate()
+        .map(|(n, l)| (n + 1, l))
+        .filter(|(_, l)| !l.trim().is_empty());
+
+    let (n, header) = rows
+        .next()
+        .ok_or(InvalidBallotFile("the file is empty".to_string()))?;
+    let mut header = parse_row(header).map_err(|e| InvalidBallotFile::at_line(n, &e))?;
+    let weighted = header
+        .first()
+        .is_some_and(|h| h.trim().eq_ignore_ascii_case("weight"));
+    if weighted {
+        header.remove(0);
+    }
+    let choices: Vec<&str> = header.iter().map(|c| c.trim()).collect();
+
+    let mut ballots = Vec::new();
+    for (n, row) in rows {
+        let mut cells = parse_row(row).map_err(|e| InvalidBallotFile::at_line(n, &e))?;
+        let weight = if weighted {
+            let w = cells.remove(0);
+            if w.trim().is_empty() {
+                1
+            } else {
+                w.trim()
+                    .parse()
+                    .map_err(|_| InvalidBallotFile::at_line(n, "invalid weight"))?
+            }
+        } else {
+            1
+        };
+        if cells.len() != choices.len() {
+            return Err(InvalidBallotFile::at_line(
+                n,
+                &format!("expected {} cells, found {}", choices.len(), cells.len()),
+            ));
+        }
+
+        let mut ranks: Vec<(u32, &str)> = Vec::new();
+        for (cell, c) in cells.iter().zip(&choices) {
+            if !cell.trim().is_empty() {
+                let rank = cell.trim().parse().map_err(|_| {
+                    InvalidBallotFile::at_line(n, &format!("invalid rank {}", cell))
+                })?;
+                ranks.push((rank, c));
+            }
+        }
+        ranks.sort_by_key(|(rank, _)| *rank);
+
+        // Candidates with the same rank are ranked equally
+        let mut groups: Vec<Vec<String>> = Vec::new();
+        for (i, (rank, c)) in ranks.iter().enumerate() {
+            if i > 0 && ranks[i - 1].0 == *rank {
+                groups.last_mut().unwrap().push(c.to_string());
+            } else {
+                groups.push(vec![c.to_string()]);
+            }
+        }
+
+        ballots.push(WeightedBallot::new(ranked_ballot(groups), weight));
+    }
+
+    Ok(RawBallots::from_weighted_ballots(&choices, ballots))
+}
+
+/// Split a row into cells, cells containing commas or quotes being between quotes
+fn parse_row(row: &str) -> Result<Vec<String>, String> {
+    let mut cells = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = row.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        let cell = cells.last_mut().unwrap();
+        match ch {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(String::new()),
+            _ => cell.push(ch),
+        }
+    }
+    if quoted {
+        return Err("unclosed quote".to_string());
+    }
+
+    Ok(cells)
+}
+
+/// Write a cell between quotes if needed
+fn cell(value: &str) -> String {
+    if value.contains([',', '"']) || value.trim() != value {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+/// Write uninominal and ranked ballots in a ballot sheet with a weight column
+pub fn write_csv(ballots: &RawBallots) -> Result<String, InvalidBallotFile> {
+    let choices = ballots.get_choices();
+
+    let mut output = String::from("weight");
+    for c in choices {
+        output += ",";
+        output += &cell(c);
+    }
+    output += "\n";
+
+    for b in ballots.get_ballots() {
+        let groups =
+            ranking(b.get_ballot()).ok_or(InvalidBallotFile::unsupported(b.get_ballot(), "CSV"))?;
+        if let Some(c) = groups.iter().flatten().find(|c| !choices.contains(c)) {
+            return Err(InvalidBallotFile(format!("unknown candidate {}", c)));
+        }
+
+        output += &b.get_weight().to_string();
+        for c in choices {
+            output += ",";
+            if let Some(rank) = groups.iter().position(|group| group.contains(c)) {
+                output += &(rank + 1).to_string();
+            }
+        }
+        output += "\n";
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voting_system::SingleBallot;
+
+    #[test]
+    fn csv_file() {
+        assert_eq!(
+            Ok(vec![
+                "a".to_string(),
+                "b, \"c\"".to_string(),
+                "".to_string()
+            ]),
+            parse_row("a,\"b, \"\"c\"\"\",")
+        );
+        assert!(parse_row("\"a").is_err());
+
+        let ballots = read_csv("A,\"B, Jr.\",C\n1,2,3\n\n,1,\n2,1,1").unwrap();
+        assert_eq!(vec!["A", "B, Jr.", "C"], ballots.get_choices());
+        assert!(matches!(
+            ballots.get_ballots()[1].get_ballot(),
+            SingleBallot::Ranked(ranked) if ranked == &vec!["B, Jr.".to_string()]
+        ));
+        assert!(matches!(
+            ballots.get_ballots()[2].get_ballot(),
+            SingleBallot::RankedWithTies(groups) if groups.len() == 2
+        ));
+
+        let written = write_csv(&ballots).unwrap();
+        assert_eq!("weight,A,\"B, Jr.\",C\n1,1,2,3\n1,,1,\n1,2,1,1\n", written);
+        assert_eq!(written, write_csv(&read_csv(&written).unwrap()).unwrap());
+
+        assert!(read_csv("A,B\n1,2,3").is_err());
+        assert!(read_csv("A,B\n1,x").is_err());
+    }
+}
@@ -0,0 +1,267 @@
+use crate::ballot_file::{InvalidBallotFile, ranked_ballot, ranking};
+use crate::voting_system::{RawBallots, WeightedBallot};
+
+/// Read PrefLib orders from a `.soc`, `.soi`, `.toc` or `.toi` file.
+/// Each order becomes one ballot carrying as many votes as voters who cast it.
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::ballot_file::read_preflib;
+///
+/// let ballots = read_preflib(concat!(
+///     "# DATA TYPE: toc\n",
+///     "# NUMBER ALTERNATIVES: 3\n",
+///     "# ALTERNATIVE NAME 1: Alice\n",
+///     "# ALTERNATIVE NAME 2: Bob\n",
+///     "# ALTERNATIVE NAME 3: Carol\n",
+///     "4: 1,2,3\n",
+///     "2: 3,{1,2}\n",
+/// ))
+/// .unwrap();
+///
+/// assert_eq!(vec!["Alice", "Bob", "Carol"], ballots.get_choices());
+/// assert_eq!(2, ballots.get_ballots()[1].get_weight());
+/// ```
+pub fn read_preflib(input: &str) -> Result<RawBallots, InvalidBallotFile> {
+    let mut number: Option<usize> = None;
+    let mut names: Vec<(usize, String)> = Vec::new();
+    let mut orders: Vec<(usize, u32, Vec<Vec<usize>>)> = Vec::new();
+
+    for (n, line) in input.lines().enumerate().map(|(n, l)| (n + 1, l.trim())) {
+        if let Some(metadata) = line.strip_prefix('#') {
+            let Some((key, value)) = metadata.split_once(':') else {
+                continue;
+            };
+            let key = key.trim();
+            let value = value.trim();
+
+            if key == "NUMBER ALTERNATIVES" {
+                number = Some(
+                    value
+                        .parse()
+                        .map_err(|_| InvalidBallotFile::at_line(n, "invalid number"))?,
+                );
+            } else if let Some(i) = key.strip_prefix("ALTERNATIVE NAME ") {
+                let i = i
+                    .trim()
+                    .parse()
+                    .map_err(|_| InvalidBallotFile::at_line(n, "invalid alternative"))?;
+                names.push((i, value.to_owned()));
+            }
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        let (count, order) = line
+            .split_once(':')
+            .ok_or(InvalidBallotFile::at_line(n, "expected <count>: <order>"))?;
+        let count = count
+            .trim()
+            .parse()
+            .map_err(|_| InvalidBallotFile::at_line(n, "invalid count"))?;
+        orders.push((
+            n,
+            count,
+            parse_order(order).map_err(|e| InvalidBallotFile::at_line(n, &e))?,
+        ));
+    }
+
+    let number = number.unwrap_or(names.len());
+    let name = |i: usize| {
+        names
+            .iter()
+            .find(|(j, _)| *j == i)
+            .map_or(i.to_string(), |(_, name)| name.to_owned())
+    };
+    let choices: Vec<String> = (1..=number).map(name).collect();
+
+    let mut ballots = Vec::new();
+    for (n, count, order) in orders {
+        if let Some(c) = order.iter().flatten().find(|c| **c == 0 || **c > number) {
+            return Err(InvalidBallotFile::at_line(
+                n,
+                &format!("unknown alternative {}", c),
+            ));
+        }
+        let groups = order
+            .into_iter()
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|c| choices[c - 1].to_owned())
+                    .collect()
+            })
+            .collect();
+        ballots.push(WeightedBallot::new(ranked_ballot(groups), count));
+    }
+
+    let choices: Vec<&str> = choices.iter().map(|c| c.as_str()).collect();
+    Ok(RawBallots::from_weighted_ballots(&choices, ballots))
+}
+
+/// Parse an order like `1,{2,3},4`, equally ranked alternatives being between braces
+fn parse_order(order: &str) -> Result<Vec<Vec<usize>>, String> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut tie: Option<Vec<usize>> = None;
+    let mut token = String::new();
+
+    for ch in order.chars().chain([',']) {
+        match ch {
+            ',' | '{' | '}' => {
+                if !token.trim().is_empty() {
+                    let c = token
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("invalid alternative {}", token.trim()))?;
+                    match &mut tie {
+                        Some(group) => group.push(c),
+                        None => groups.push(vec![c]),
+                    }
+                }
+                token.clear();
+
+                match (ch, tie.take()) {
+                    ('{', None) => tie = Some(Vec::new()),
+                    ('}', Some(group)) => groups.push(group),
+                    (',', group) => tie = group,
+                    _ => return Err("unbalanced braces".to_string()),
+                }
+            }
+            _ => token.push(ch),
+        }
+    }
+    if tie.is_some() {
+        return Err("unbalanced braces".to_string());
+    }
+
+    Ok(groups
+        .into_iter()
+        .filter(|group| !group.is_empty())
+        .collect())
+}
+
+/// Write uninominal and ranked ballots as PrefLib orders,
+/// the data type depending on whether ballots are complete and have equal rankings
+pub fn write_preflib(ballots: &RawBallots) -> Result<String, InvalidBallotFile> {
+    let choices = ballots.get_choices();
+
+    // Identical orders are counted together, in the order they first appear
+    let mut orders: Vec<(Vec<Vec<usize>>, u32)> = Vec::new();
+    for b in ballots.get_ballots() {
+        let order = ranking(b.get_ballot())
+            .and_then(|groups| {
+                groups
+                    .iter()
+                    .map(|group| {
+                        group
+                            .iter()
+                            .map(|c| choices.iter().position(|x| x == c).map(|i| i + 1))
+                            .collect::<Option<Vec<usize>>>()
+                    })
+                    .collect::<Option<Vec<Vec<usize>>>>()
+            })
+            .ok_or(InvalidBallotFile::unsupported(b.get_ballot(), "PrefLib"))?;
+
+        match orders.iter_mut().find(|(o, _)| *o == order) {
+            Some((_, count)) => *count += b.get_weight(),
+            None => orders.push((order, b.get_weight())),
+        }
+    }
+    orders.sort_by(|(_, c1), (_, c2)| c2.cmp(c1));
+
+    let complete = orders
+        .iter()
+        .all(|(o, _)| o.iter().map(|group| group.len()).sum::<usize>() == choices.len());
+    let ties = orders
+        .iter()
+        .any(|(o, _)| o.iter().any(|group| group.len() > 1));
+    let data_type = match (ties, complete) {
+        (false, true) => "soc",
+        (false, false) => "soi",
+        (true, true) => "toc",
+        (true, false) => "toi",
+    };
+
+    let mut output = format!(
+        "# DATA TYPE: {}\n# NUMBER ALTERNATIVES: {}\n",
+        data_type,
+        choices.len()
+    );
+    for (i, c) in choices.iter().enumerate() {
+        output += &format!("# ALTERNATIVE NAME {}: {}\n", i + 1, c);
+    }
+    output += &format!(
+        "# NUMBER VOTERS: {}\n# NUMBER UNIQUE ORDERS: {}\n",
+        orders.iter().map(|(_, c)| c).sum::<u32>(),
+        orders.len()
+    );
+
+    for (order, count) in orders {
+        let order: Vec<String> = order
+            .iter()
+            .map(|group| {
+                let group: Vec<String> = group.iter().map(|c| c.to_string()).collect();
+                if group.len() > 1 {
+                    format!("{{{}}}", group.join(","))
+                } else {
+                    group.join(",")
+                }
+            })
+            .collect();
+        output += &format!("{}: {}\n", count, order.join(","));
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preflib_file() {
+        assert_eq!(
+            Ok(vec![vec![1], vec![2, 3], vec![4]]),
+            parse_order("1,{2,3},4")
+        );
+        assert_eq!(Ok(vec![vec![2, 1]]), parse_order("{2, 1}"));
+        assert!(parse_order("1,{2,3").is_err());
+        assert!(parse_order("1}").is_err());
+
+        let input = "# FILE NAME: 00001-00000001.soi
+            # DATA TYPE: soi
+            # NUMBER ALTERNATIVES: 3
+            # ALTERNATIVE NAME 1: A
+            # ALTERNATIVE NAME 2: B
+            # ALTERNATIVE NAME 3: C
+            5: 1,2,3
+            3: 2
+            2: 3,1";
+        let ballots = read_preflib(input).unwrap();
+        assert_eq!(vec!["A", "B", "C"], ballots.get_choices());
+        assert_eq!(
+            vec![5, 3, 2],
+            ballots
+                .get_ballots()
+                .iter()
+                .map(|b| b.get_weight())
+                .collect::<Vec<u32>>()
+        );
+
+        let written = write_preflib(&ballots).unwrap();
+        assert!(written.starts_with("# DATA TYPE: soi\n"));
+        assert!(
+            written.ends_with(
+                "# NUMBER VOTERS: 10\n# NUMBER UNIQUE ORDERS: 3\n5: 1,2,3\n3: 2\n2: 3,1\n"
+            )
+        );
+        assert_eq!(
+            written,
+            write_preflib(&read_preflib(&written).unwrap()).unwrap()
+        );
+
+        assert!(read_preflib("# NUMBER ALTERNATIVES: 2\n1: 1,3").is_err());
+    }
+}
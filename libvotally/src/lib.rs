@@ -3,3 +3,5 @@
 pub mod voting_system;
 
 pub mod network;
+
+pub mod ballot_file;
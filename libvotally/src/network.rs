@@ -1,5 +1,19 @@
+pub mod protocol;
+
+mod discovery;
+pub use self::discovery::{DISCOVERY_PORT, DiscoveredServer};
+
+mod error;
+pub use self::error::Error;
+
 mod server;
 pub use self::server::VotallyServer;
 
 mod client;
-pub use self::client::VotallyClient;
+pub use self::client::{VotallyClient, split_host_port};
+
+mod voter_roll;
+pub use self::voter_roll::{InvalidVoterRoll, VoterCredentials, VoterRoll};
+
+mod election_definition;
+pub use self::election_definition::{Candidate, ElectionDefinition, InvalidElectionDefinition};
@@ -1,65 +1,181 @@
-use tokio::{
-    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
-    net::TcpStream,
-};
+use std::time::Duration;
+
+use tokio::net::{TcpStream, ToSocketAddrs};
 
 use crate::{
-    network::server::VotallyServer,
-    voting_system::{MinimalVotingSystemInfo, SingleBallot},
+    network::protocol::{
+        ElectionInfo, Message, PROTOCOL_VERSION, read_message, version_mismatch, write_message,
+    },
+    network::{DiscoveredServer, Error, VoterCredentials, discovery},
+    voting_system::{ElectionResult, SingleBallot},
 };
 
 pub struct VotallyClient {
     stream: TcpStream,
+    /// Name and token of the voter, to find its weight in the voter roll of the server
+    voter: Option<VoterCredentials>,
+    voting_open: bool,
+    /// Result received while waiting for the answer to a ballot
+    result: Option<ElectionResult>,
 }
 
 impl VotallyClient {
-    /// Create a new VotalClient
-    pub async fn new(address: String) -> Self {
-        let stream = TcpStream::connect(address + ":" + VotallyServer::PORT)
-            .await
-            .unwrap();
+    /// Create a new VotalClient and check that the server uses the same protocol
+    /// `address` is the address of the server, like `(host, VotallyServer::PORT)`
+    pub async fn new<A: ToSocketAddrs>(address: A) -> Result<Self, Error> {
+        let stream = TcpStream::connect(address).await.map_err(Error::Connect)?;
+
+        let mut client = Self {
+            stream,
+            voter: None,
+            voting_open: false,
+            result: None,
+        };
+        client
+            .write_stream(Message::Hello {
+                version: PROTOCOL_VERSION,
+            })
+            .await?;
+        match client.read_stream().await? {
+            Message::Hello {
+                version: PROTOCOL_VERSION,
+            } => {}
+            Message::Hello { version } => {
+                return Err(Error::Protocol(version_mismatch("server", version)));
+            }
+            message => return Err(unexpected(message)),
+        }
 
-        Self { stream }
+        Ok(client)
     }
 
-    /// Write message in TcpStream
-    async fn write_stream(&mut self, message: String) {
-        let message = format!("{message}\n");
+    /// List the servers of the local network which still accept voters,
+    /// waiting `timeout` for their answers
+    pub async fn discover(timeout: Duration) -> Result<Vec<DiscoveredServer>, Error> {
+        discovery::discover(timeout).await.map_err(Error::Io)
+    }
 
-        self.stream.write_all(message.as_bytes()).await.unwrap();
+    /// Vote under the name of a voter of the voter roll
+    pub fn with_voter(mut self, credentials: VoterCredentials) -> Self {
+        self.voter = Some(credentials);
+        self
     }
 
-    /// Read one line of TcpStream
-    async fn read_stream(&mut self) -> String {
-        let mut buffer = String::new();
-        let mut reader = BufReader::new(&mut self.stream);
-        reader.read_line(&mut buffer).await.unwrap();
+    /// Write message in TcpStream
+    async fn write_stream(&mut self, message: Message) -> Result<(), Error> {
+        Ok(write_message(&mut self.stream, &message).await?)
+    }
 
-        buffer
+    /// Read one message of TcpStream
+    async fn read_stream(&mut self) -> Result<Message, Error> {
+        Ok(read_message(&mut self.stream).await?)
     }
 
     /// Get all the information from server
-    pub async fn get_info(&mut self) -> MinimalVotingSystemInfo {
-        ron::de::from_str::<MinimalVotingSystemInfo>(&(self.read_stream().await))
-            .unwrap()
-            .shuffle_choices()
+    pub async fn get_info(&mut self) -> Result<ElectionInfo, Error> {
+        match self.read_stream().await? {
+            Message::ElectionInfo(info) => Ok(info.shuffle_choices()),
+            message => Err(unexpected(message)),
+        }
     }
 
-    /// Send the vote to the server
-    pub async fn send_vote(&mut self, ballot: &SingleBallot) {
-        let _ = self.read_stream().await; // expect a blank line
+    /// Send the vote to the server once voting is open
+    /// Return `Error::BallotRejected` with the reason given by the server if the ballot is rejected,
+    /// another ballot can then be sent while voting is open
+    pub async fn send_vote(&mut self, ballot: &SingleBallot) -> Result<(), Error> {
+        if self.is_voting_closed() {
+            return Err(Error::BallotRejected("Voting is closed".to_string()));
+        }
+        if !self.voting_open {
+            match self.read_stream().await? {
+                Message::VotingOpen => self.voting_open = true,
+                message => return Err(unexpected(message)),
+            }
+        }
+
+        self.write_stream(Message::Ballot {
+            voter: self.voter.clone(),
+            ballot: ballot.clone(),
+        })
+        .await?;
+
+        match self.read_stream().await? {
+            Message::BallotAccepted => Ok(()),
+            Message::BallotRejected(reason) => Err(Error::BallotRejected(reason)),
+            Message::Result(result) => {
+                self.result = Some(result);
+                Err(Error::BallotRejected("Voting is closed".to_string()))
+            }
+            message => Err(unexpected(message)),
+        }
+    }
 
-        let ser_singleballot = ron::ser::to_string(&ballot).unwrap() + "\n";
-        self.write_stream(ser_singleballot).await;
+    /// Check if the server has ended voting
+    pub fn is_voting_closed(&self) -> bool {
+        self.result.is_some()
     }
 
     /// Get the result
-    pub async fn result(&mut self) -> String {
-        let res = self.read_stream().await;
+    pub async fn result(&mut self) -> Result<ElectionResult, Error> {
+        if let Some(result) = self.result.take() {
+            return Ok(result);
+        }
+
+        match self.read_stream().await? {
+            Message::Result(result) => Ok(result),
+            message => Err(unexpected(message)),
+        }
+    }
+}
+
+/// Error for a message the server was not expected to send
+fn unexpected(message: Message) -> Error {
+    match message {
+        Message::Error(error) => Error::Protocol(error),
+        message => Error::Protocol(format!(
+            "Unexpected message {} from the server",
+            message.name()
+        )),
+    }
+}
+
+/// Split an address typed by a voter into a host and a port,
+/// the port being `default_port` if it is left out.
+/// IPv6 addresses are written between brackets when followed by a port.
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::network::split_host_port;
+///
+/// assert_eq!(Some(("192.168.1.2".to_string(), 50001)), split_host_port("192.168.1.2", 50001));
+/// assert_eq!(Some(("votally.lan".to_string(), 6000)), split_host_port("votally.lan:6000", 50001));
+/// assert_eq!(Some(("fe80::1".to_string(), 50001)), split_host_port("fe80::1", 50001));
+/// assert_eq!(Some(("::1".to_string(), 6000)), split_host_port("[::1]:6000", 50001));
+/// assert_eq!(None, split_host_port("192.168.1.2:port", 50001));
+/// ```
+pub fn split_host_port(address: &str, default_port: u16) -> Option<(String, u16)> {
+    let address = address.trim();
+
+    let (host, port) = if let Some(rest) = address.strip_prefix('[') {
+        let (host, rest) = rest.split_once(']')?;
+        match rest {
+            "" => (host, None),
+            _ => (host, Some(rest.strip_prefix(':')?)),
+        }
+    } else {
+        match address.split_once(':') {
+            // Only IPv6 addresses contain several colons
+            Some((host, port)) if !port.contains(':') => (host, Some(port)),
+            _ => (address, None),
+        }
+    };
 
-        // remove the newline at the end
-        let mut res = res.chars();
-        res.next_back();
-        res.as_str().to_owned()
+    if host.is_empty() {
+        return None;
     }
+    let port = match port {
+        Some(port) => port.parse().ok()?,
+        None => default_port,
+    };
+    Some((host.to_owned(), port))
 }
@@ -0,0 +1,206 @@
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use serde::{Deserialize, Serialize};
+use socket2::{Domain, Protocol, Socket, Type};
+use tokio::{io, net::UdpSocket, sync::watch};
+
+use crate::network::protocol::PROTOCOL_VERSION;
+
+/// UDP port on which servers answer the clients looking for them
+pub const DISCOVERY_PORT: u16 = 50002;
+
+/// Largest datagram exchanged during discovery
+const MAX_DATAGRAM_LENGTH: usize = 4096;
+
+/// Datagram broadcast by clients and answered by servers which can still accept voters
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) enum Discovery {
+    /// A client looks for the servers using its version of the protocol
+    Query { version: u32 },
+    /// A server describes its election
+    Announcement(Announcement),
+}
+
+/// Election of a server and where to reach it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct Announcement {
+    pub(crate) title: String,
+    pub(crate) method: String,
+    /// Address the server listens on, unspecified if it listens on every interface
+    pub(crate) host: String,
+    pub(crate) port: u16,
+}
+
+/// Server found on the local network
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiscoveredServer {
+    title: String,
+    method: String,
+    address: SocketAddr,
+}
+
+impl DiscoveredServer {
+    /// Title of the election
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+
+    /// Name of the voting system of the election
+    pub fn get_method(&self) -> &str {
+        &self.method
+    }
+
+    /// Address to give to VotallyClient::new
+    pub fn get_address(&self) -> SocketAddr {
+        self.address
+    }
+}
+
+impl fmt::Display for DiscoveredServer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({}) at {}", self.title, self.method, self.address)
+    }
+}
+
+/// Encode a datagram in RON
+fn encode(discovery: &Discovery) -> io::Result<Vec<u8>> {
+    ron::ser::to_string(discovery)
+        .map(String::into_bytes)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Decode a datagram, ignoring the ones which aren't from Votally
+fn decode(datagram: &[u8]) -> Option<Discovery> {
+    ron::de::from_bytes(datagram).ok()
+}
+
+/// Open the socket receiving the queries on `port` for a server listening on `address`,
+/// on every interface of its address family so that broadcast queries reach it.
+/// The port is shared with the other servers of this computer.
+pub(crate) fn discovery_socket(address: SocketAddr, port: u16) -> io::Result<UdpSocket> {
+    let any: IpAddr = match address {
+        SocketAddr::V4(_) => Ipv4Addr::UNSPECIFIED.into(),
+        SocketAddr::V6(_) => Ipv6Addr::UNSPECIFIED.into(),
+    };
+
+    let socket = Socket::new(
+        Domain::for_address(address),
+        Type::DGRAM,
+        Some(Protocol::UDP),
+    )?;
+    if address.is_ipv6() {
+        // Queries are broadcast over IPv4
+        socket.set_only_v6(false)?;
+    }
+    socket.set_reuse_address(true)?;
+    #[cfg(unix)]
+    socket.set_reuse_port(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&SocketAddr::new(any, port).into())?;
+
+    UdpSocket::from_std(socket.into())
+}
+
+/// Answer to the queries of clients until voting starts
+pub(crate) async fn answer_discovery(
+    socket: UdpSocket,
+    announcement_rx: watch::Receiver<Announcement>,
+    mut end_accept_voter_rx: watch::Receiver<()>,
+) {
+    let mut buffer = vec![0; MAX_DATAGRAM_LENGTH];
+
+    tokio::select! {
+    _ = async {
+        while let Ok((length, client)) = socket.recv_from(&mut buffer).await {
+            if let Some(Discovery::Query {
+                version: PROTOCOL_VERSION,
+            }) = decode(&buffer[..length])
+            {
+                let announcement = Discovery::Announcement(announcement_rx.borrow().clone());
+                if let Ok(datagram) = encode(&announcement) {
+                    let _ = socket.send_to(&datagram, client).await;
+                }
+            }
+        }
+    } => {}
+    _ = end_accept_voter_rx.changed() => {}
+    }
+}
+
+/// Broadcast a query on the local network and gather the answers until `timeout`
+pub(crate) async fn discover(timeout: std::time::Duration) -> io::Result<Vec<DiscoveredServer>> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+    socket.set_broadcast(true)?;
+
+    let query = encode(&Discovery::Query {
+        version: PROTOCOL_VERSION,
+    })?;
+    socket
+        .send_to(&query, ("255.255.255.255", DISCOVERY_PORT))
+        .await?;
+
+    let mut servers: Vec<DiscoveredServer> = Vec::new();
+    let mut buffer = vec![0; MAX_DATAGRAM_LENGTH];
+    let gather = async {
+        while let Ok((length, sender)) = socket.recv_from(&mut buffer).await {
+            if let Some(Discovery::Announcement(Announcement {
+                title,
+                method,
+                host,
+                port,
+            })) = decode(&buffer[..length])
+            {
+                // A server listening on every interface is reached where it answered from
+                let ip = match host.parse::<IpAddr>() {
+                    Ok(ip) if !ip.is_unspecified() => ip,
+                    _ => sender.ip(),
+                };
+                let server = DiscoveredServer {
+                    title,
+                    method,
+                    address: SocketAddr::new(ip, port),
+                };
+                if !servers.contains(&server) {
+                    servers.push(server);
+                }
+            }
+        }
+    };
+    let _ = tokio::time::timeout(timeout, gather).await;
+
+    Ok(servers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn datagrams() {
+        let announcement = Discovery::Announcement(Announcement {
+            title: "Board".to_string(),
+            method: "plurality".to_string(),
+            host: "192.168.1.2".to_string(),
+            port: 50001,
+        });
+
+        assert!(matches!(
+            decode(&encode(&announcement).unwrap()),
+            Some(Discovery::Announcement(Announcement { port: 50001, .. }))
+        ));
+        assert!(decode(b"not votally").is_none());
+    }
+
+    #[tokio::test]
+    async fn shared_port() {
+        let address: SocketAddr = "192.168.1.2:50001".parse().unwrap();
+
+        // An ephemeral port stands for DISCOVERY_PORT, which another program may be using
+        let first = discovery_socket(address, 0).unwrap();
+        let port = first.local_addr().unwrap().port();
+        let second = discovery_socket(address, port).unwrap();
+        assert_eq!(port, second.local_addr().unwrap().port());
+        assert!(first.local_addr().unwrap().ip().is_unspecified());
+    }
+}
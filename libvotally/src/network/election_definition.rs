@@ -0,0 +1,297 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::network::{VotallyServer, VoterRoll};
+use crate::voting_system::{VotingParameters, check_seats, correct_voting_system};
+
+/// Candidate of an election
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Candidate {
+    /// Name used on the ballots
+    name: String,
+    /// Presentation of the candidate
+    #[serde(default)]
+    description: String,
+}
+
+impl Candidate {
+    /// Create a new candidate
+    pub fn new(name: &str, description: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            description: description.to_owned(),
+        }
+    }
+
+    /// Get the name used on the ballots
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the presentation of the candidate
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+}
+
+/// Everything needed to hold an election with a server, written in RON
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::network::ElectionDefinition;
+///
+/// let definition: ElectionDefinition = r#"(
+///     title: "Board election",
+///     candidates: [
+///         (name: "Alice", description: "Treasurer since 2020"),
+///         (name: "Bob"),
+///         (name: "Carol"),
+///     ],
+///     method: "stv",
+///     parameters: (seats: 2),
+///     voter_roll: Some({
+///         "Dave": (weight: 3, token: "k7Fq2"),
+///         "Erin": (token: "x9Zt4"),
+///     }),
+/// )"#
+/// .parse()
+/// .unwrap();
+///
+/// assert_eq!(vec!["Alice", "Bob", "Carol"], definition.get_choices());
+/// assert_eq!(2, definition.get_parameters().get_seats());
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ElectionDefinition {
+    /// Title of the election
+    title: String,
+    /// What the election is about
+    #[serde(default)]
+    description: String,
+    /// Candidates, in the order they are presented
+    candidates: Vec<Candidate>,
+    /// Name of the voting system
+    method: String,
+    /// Parameters of the voting system
+    #[serde(default)]
+    parameters: VotingParameters,
+    /// Port the server listens on
+    #[serde(default)]
+    port: Option<u16>,
+    /// Voters allowed to vote and their weights
+    #[serde(default)]
+    voter_roll: Option<VoterRoll>,
+}
+
+impl ElectionDefinition {
+    /// Create a new election definition
+    pub fn new(title: &str, candidates: Vec<Candidate>, method: &str) -> Self {
+        Self {
+            title: title.to_owned(),
+            description: String::new(),
+            candidates,
+            method: method.to_owned(),
+            parameters: VotingParameters::default(),
+            port: None,
+            voter_roll: None,
+        }
+    }
+
+    /// Set what the election is about
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = description.to_owned();
+        self
+    }
+
+    /// Set the parameters of the voting system
+    pub fn with_parameters(mut self, parameters: VotingParameters) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    /// Set the port the server listens on
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Set the voters allowed to vote and their weights
+    pub fn with_voter_roll(mut self, voter_roll: VoterRoll) -> Self {
+        self.voter_roll = Some(voter_roll);
+        self
+    }
+
+    /// Get the title of the election
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+
+    /// Get what the election is about
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    /// Get the candidates
+    pub fn get_candidates(&self) -> &[Candidate] {
+        &self.candidates
+    }
+
+    /// Get the names of the candidates
+    pub fn get_choices(&self) -> Vec<&str> {
+        self.candidates.iter().map(|c| c.get_name()).collect()
+    }
+
+    /// Get the name of the voting system
+    pub fn get_method(&self) -> &str {
+        &self.method
+    }
+
+    /// Get the parameters of the voting system
+    pub fn get_parameters(&self) -> &VotingParameters {
+        &self.parameters
+    }
+
+    /// Get the port the server listens on
+    pub fn get_port(&self) -> u16 {
+        self.port.unwrap_or(VotallyServer::PORT)
+    }
+
+    /// Get the voters allowed to vote and their weights
+    pub fn get_voter_roll(&self) -> Option<&VoterRoll> {
+        self.voter_roll.as_ref()
+    }
+
+    /// Check that an election can be held with this definition
+    pub fn validate(&self) -> Result<(), InvalidElectionDefinition> {
+        let invalid = |message: String| Err(InvalidElectionDefinition(message));
+
+        if self.title.trim().is_empty() {
+            return invalid("the title is empty".to_string());
+        }
+
+        if correct_voting_system(&self.method).is_err() {
+            return invalid(format!("unknown voting system {}", self.method));
+        }
+
+        if let Err(e) = self.parameters.validate(&self.get_choices()) {
+            return invalid(e.0);
+        }
+        if let Err(e) = check_seats(&self.method, &self.parameters) {
+            return invalid(e.0);
+        }
+
+        if self.port == Some(0) {
+            return invalid("the port can't be 0".to_string());
+        }
+
+        if let Some(roll) = &self.voter_roll {
+            if roll.is_empty() {
+                return invalid("the voter roll is empty".to_string());
+            }
+            if let Some((name, _)) = roll.voters().find(|(_, weight)| *weight == 0) {
+                return invalid(format!("{} carries no vote in the voter roll", name));
+            }
+            if let Some(name) = roll.voters_without_token().next() {
+                return invalid(format!("{} has no token in the voter roll", name));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for ElectionDefinition {
+    type Err = InvalidElectionDefinition;
+
+    /// Parse and validate a definition written in RON
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let definition: ElectionDefinition =
+            ron::de::from_str(s).map_err(|e| InvalidElectionDefinition(e.to_string()))?;
+        definition.validate()?;
+
+        Ok(definition)
+    }
+}
+
+/// Error for invalid election definition
+#[derive(Debug)]
+pub struct InvalidElectionDefinition(String);
+
+impl fmt::Display for InvalidElectionDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid election definition: {}", self.0)
+    }
+}
+
+impl Error for InvalidElectionDefinition {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn election_definition() {
+        let definition = ElectionDefinition::new(
+            "Treasurer",
+            vec![Candidate::new("A", "First"), Candidate::new("B", "")],
+            "irv",
+        )
+        .with_port(50002);
+        assert!(definition.validate().is_ok());
+        assert_eq!(50002, definition.get_port());
+
+        // Written and read back in RON
+        let written = ron::ser::to_string(&definition).unwrap();
+        let read: ElectionDefinition = written.parse().unwrap();
+        assert_eq!(definition.get_candidates(), read.get_candidates());
+        assert_eq!(definition.get_parameters(), read.get_parameters());
+
+        let unknown = ElectionDefinition::new(
+            "Treasurer",
+            vec![Candidate::new("A", ""), Candidate::new("B", "")],
+            "unknown",
+        );
+        assert!(unknown.validate().is_err());
+
+        let repeated = ElectionDefinition::new(
+            "Treasurer",
+            vec![Candidate::new("A", ""), Candidate::new("A", "")],
+            "irv",
+        );
+        assert!(repeated.validate().is_err());
+
+        let seats = ElectionDefinition::new(
+            "Treasurer",
+            vec![Candidate::new("A", ""), Candidate::new("B", "")],
+            "stv",
+        )
+        .with_parameters(VotingParameters::default().with_seats(2));
+        assert!(seats.validate().is_err());
+
+        let three = vec![
+            Candidate::new("A", ""),
+            Candidate::new("B", ""),
+            Candidate::new("C", ""),
+        ];
+        let two_seats = VotingParameters::default().with_seats(2);
+        assert!(
+            ElectionDefinition::new("Board", three.clone(), "stv")
+                .with_parameters(two_seats.clone())
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            ElectionDefinition::new("Board", three, "plurality")
+                .with_parameters(two_seats)
+                .validate()
+                .is_err()
+        );
+
+        assert!(
+            "(title: \"Treasurer\")"
+                .parse::<ElectionDefinition>()
+                .is_err()
+        );
+    }
+}
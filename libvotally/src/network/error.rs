@@ -0,0 +1,83 @@
+use std::error;
+use std::fmt;
+
+use tokio::{io, task::JoinError};
+
+use crate::network::InvalidElectionDefinition;
+use crate::voting_system::UnknownVotingSystem;
+
+/// Error of a client or a server
+#[derive(Debug)]
+pub enum Error {
+    /// The server can't be reached
+    Connect(io::Error),
+    /// The server can't listen on its address
+    Bind(io::Error),
+    /// The connection was lost during the exchange
+    Io(io::Error),
+    /// A message can't be read
+    Decode(String),
+    /// The other side doesn't follow the protocol or stopped the exchange
+    Protocol(String),
+    /// The server didn't count the ballot, for the given reason
+    BallotRejected(String),
+    /// The server no longer accepts voters because voting has already started
+    VotingStarted,
+    /// The server doesn't know the voting system of the election
+    UnknownVotingSystem(UnknownVotingSystem),
+    /// The server stopped before counting the ballots
+    Count(JoinError),
+    /// The server can't hold the election of its definition
+    InvalidElection(InvalidElectionDefinition),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Connect(e) => write!(f, "Can't connect to the server: {}", e),
+            Error::Bind(e) => write!(f, "Can't listen for voters: {}", e),
+            Error::Io(e) => write!(f, "Connection lost: {}", e),
+            Error::Decode(e) => write!(f, "Invalid message: {}", e),
+            Error::Protocol(e) => write!(f, "{}", e),
+            Error::BallotRejected(e) => write!(f, "Ballot rejected: {}", e),
+            Error::VotingStarted => write!(f, "Voting has already started"),
+            Error::UnknownVotingSystem(e) => write!(f, "{}", e),
+            Error::Count(e) => write!(f, "The ballots can't be counted: {}", e),
+            Error::InvalidElection(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Connect(e) | Error::Bind(e) | Error::Io(e) => Some(e),
+            Error::UnknownVotingSystem(e) => Some(e),
+            Error::Count(e) => Some(e),
+            Error::InvalidElection(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Messages which can't be decoded are read as invalid data
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::InvalidData => Error::Decode(e.to_string()),
+            _ => Error::Io(e),
+        }
+    }
+}
+
+impl From<UnknownVotingSystem> for Error {
+    fn from(e: UnknownVotingSystem) -> Self {
+        Error::UnknownVotingSystem(e)
+    }
+}
+
+impl From<InvalidElectionDefinition> for Error {
+    fn from(e: InvalidElectionDefinition) -> Self {
+        Error::InvalidElection(e)
+    }
+}
@@ -0,0 +1,247 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use tokio::io::{self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::network::{Candidate, VoterCredentials};
+use crate::voting_system::{ElectionResult, MinimalVotingSystemInfo, SingleBallot};
+
+/// Version of the protocol, changed whenever messages change in a release
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Largest message sent or accepted, to avoid allocating a huge buffer for a corrupted length
+const MAX_MESSAGE_LENGTH: u32 = 1 << 20;
+
+/// Message exchanged between a client and a server.
+///
+/// Both sides first send `Hello`, then the server sends `ElectionInfo`
+/// and `VotingOpen` when voting starts.
+/// The client sends a `Ballot`, answered with `BallotAccepted` or `BallotRejected`,
+/// and can send another ballot after a rejection until voting ends.
+/// The server finally sends the `Result`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Message {
+    /// First message of both sides, with the version of their protocol
+    Hello { version: u32 },
+    /// Title, candidates, voting system and parameters of the election
+    ElectionInfo(ElectionInfo),
+    /// Ballots can be cast
+    VotingOpen,
+    /// Ballot of a voter, with its name and token on the voter roll if there is one
+    Ballot {
+        voter: Option<VoterCredentials>,
+        ballot: SingleBallot,
+    },
+    /// The ballot was counted
+    BallotAccepted,
+    /// The ballot was not counted, for the given reason
+    BallotRejected(String),
+    /// Result of the election
+    Result(ElectionResult),
+    /// The exchange can't go on, for the given reason
+    Error(String),
+}
+
+impl Message {
+    /// Name of the message, to report unexpected messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            Message::Hello { .. } => "Hello",
+            Message::ElectionInfo(_) => "ElectionInfo",
+            Message::VotingOpen => "VotingOpen",
+            Message::Ballot { .. } => "Ballot",
+            Message::BallotAccepted => "BallotAccepted",
+            Message::BallotRejected(_) => "BallotRejected",
+            Message::Result(_) => "Result",
+            Message::Error(_) => "Error",
+        }
+    }
+}
+
+/// Election presented to the voters by the server
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ElectionInfo {
+    title: String,
+    description: String,
+    candidates: Vec<Candidate>,
+    voting_system: MinimalVotingSystemInfo,
+    /// Ballots must carry the name and token of a voter on the voter roll
+    voter_roll: bool,
+}
+
+impl ElectionInfo {
+    /// Create the information of an election whose candidates have no description
+    pub(crate) fn new(title: &str, voting_system: MinimalVotingSystemInfo) -> Self {
+        Self {
+            title: title.to_owned(),
+            description: String::new(),
+            candidates: voting_system
+                .get_choices()
+                .iter()
+                .map(|c| Candidate::new(c, ""))
+                .collect(),
+            voting_system,
+            voter_roll: false,
+        }
+    }
+
+    /// Get the title of the election
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+
+    /// Get what the election is about
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    /// Get the candidates and their presentation
+    pub fn get_candidates(&self) -> &[Candidate] {
+        &self.candidates
+    }
+
+    /// Get the choices, voting system and parameters used to check and cast ballots
+    pub fn get_voting_system(&self) -> &MinimalVotingSystemInfo {
+        &self.voting_system
+    }
+
+    /// Check if voters have to give their name and token on the voter roll
+    pub fn has_voter_roll(&self) -> bool {
+        self.voter_roll
+    }
+
+    pub(crate) fn set_title(&mut self, title: &str) {
+        self.title = title.to_owned();
+    }
+
+    pub(crate) fn set_description(&mut self, description: &str) {
+        self.description = description.to_owned();
+    }
+
+    pub(crate) fn set_candidates(&mut self, candidates: &[Candidate]) {
+        self.candidates = candidates.to_vec();
+    }
+
+    pub(crate) fn set_voter_roll(&mut self, voter_roll: bool) {
+        self.voter_roll = voter_roll;
+    }
+
+    /// Present the choices in a random order
+    pub(crate) fn shuffle_choices(mut self) -> Self {
+        self.voting_system = self.voting_system.shuffle_choices();
+        self
+    }
+}
+
+impl fmt::Display for ElectionInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.title)?;
+        if !self.description.is_empty() {
+            writeln!(f, "{}", self.description)?;
+        }
+        // Candidates follow the order of the choices, which may be shuffled
+        for c in self.voting_system.get_choices() {
+            if let Some(candidate) = self.candidates.iter().find(|candidate| {
+                candidate.get_name() == c && !candidate.get_description().is_empty()
+            }) {
+                writeln!(f, "- {}: {}", c, candidate.get_description())?;
+            }
+        }
+        write!(f, "{}", self.voting_system)
+    }
+}
+
+/// Write a message written in RON, preceded by its length on 4 bytes in big endian
+pub async fn write_message<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    message: &Message,
+) -> io::Result<()> {
+    let encoded =
+        ron::ser::to_string(message).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    // The other side would refuse the message
+    if encoded.len() > MAX_MESSAGE_LENGTH as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("message of {} bytes is too long", encoded.len()),
+        ));
+    }
+
+    writer.write_u32(encoded.len() as u32).await?;
+    writer.write_all(encoded.as_bytes()).await?;
+    writer.flush().await
+}
+
+/// Read a message written by write_message
+pub async fn read_message<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<Message> {
+    decode(&read_frame(reader).await?)
+}
+
+/// Read the RON text of one message, without decoding it
+pub(crate) async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<String> {
+    let length = reader.read_u32().await?;
+    if length > MAX_MESSAGE_LENGTH {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("message of {} bytes is too long", length),
+        ));
+    }
+
+    let mut buffer = vec![0; length as usize];
+    reader.read_exact(&mut buffer).await?;
+
+    String::from_utf8(buffer).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Decode a message read by read_frame
+pub(crate) fn decode(frame: &str) -> io::Result<Message> {
+    ron::de::from_str(frame).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Error for a peer using another version of the protocol
+pub(crate) fn version_mismatch(peer: &str, version: u32) -> String {
+    format!(
+        "The {} uses protocol version {} but version {} is expected, update Votally",
+        peer, version, PROTOCOL_VERSION
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn framing() {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+
+        write_message(&mut client, &Message::Hello { version: 1 })
+            .await
+            .unwrap();
+        write_message(
+            &mut client,
+            &Message::Ballot {
+                voter: None,
+                ballot: SingleBallot::Uninominal("A".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            read_message(&mut server).await.unwrap(),
+            Message::Hello { version: 1 }
+        ));
+        assert!(matches!(
+            read_message(&mut server).await.unwrap(),
+            Message::Ballot { voter: None, ballot: SingleBallot::Uninominal(c) } if c == "A"
+        ));
+
+        // A length without the message
+        client.write_u32(10).await.unwrap();
+        drop(client);
+        assert!(read_message(&mut server).await.is_err());
+
+        // A message the other side would refuse isn't sent
+        let (mut client, _server) = tokio::io::duplex(1024);
+        let too_long = Message::Error("a".repeat(MAX_MESSAGE_LENGTH as usize));
+        assert!(write_message(&mut client, &too_long).await.is_err());
+    }
+}
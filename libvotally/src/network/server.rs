@@ -1,81 +1,229 @@
 use tokio::{
-    io::{self, AsyncBufReadExt, AsyncWriteExt, BufReader},
-    net::{TcpListener, TcpStream},
+    io,
+    net::{TcpListener, TcpStream, ToSocketAddrs},
     sync::{mpsc, oneshot, watch},
     task::JoinHandle,
 };
 
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use crate::network::discovery::{Announcement, DISCOVERY_PORT, answer_discovery, discovery_socket};
+use crate::network::protocol::{
+    ElectionInfo, Message, PROTOCOL_VERSION, decode, read_frame, read_message, version_mismatch,
+    write_message,
+};
+use crate::network::{Candidate, ElectionDefinition, Error, VoterCredentials, VoterRoll};
 use crate::voting_system::{
-    MinimalVotingSystemInfo, SingleBallot, UnknownVotingSystem, correct_voting_system,
-    find_voting_system,
+    ElectionResult, RawBallots, SingleBallot, VotingParameters, WeightedBallot, find_voting_system,
 };
 
+/// Ballot of a voter sent to the vote task, with the channel answering whether it was counted
+type BallotRequest = (
+    Option<VoterCredentials>,
+    SingleBallot,
+    oneshot::Sender<Result<(), String>>,
+);
+
 /// Answer to one votally client
-/// Give information then receive ballot
+/// Give information then receive ballots until one is accepted
 async fn answer_votally_client(
     mut socket: TcpStream,
     mut end_accept_voter_rx: watch::Receiver<()>,
-    ballots_tx: mpsc::Sender<SingleBallot>,
-    choices: MinimalVotingSystemInfo,
-    mut result_rx: watch::Receiver<String>,
+    ballots_tx: mpsc::Sender<BallotRequest>,
+    election_info_rx: watch::Receiver<ElectionInfo>,
+    mut result_rx: watch::Receiver<Option<ElectionResult>>,
 ) -> io::Result<()> {
-    let (socket_rd, mut socket_wr) = socket.split();
+    write_message(
+        &mut socket,
+        &Message::Hello {
+            version: PROTOCOL_VERSION,
+        },
+    )
+    .await?;
+    match read_message(&mut socket).await? {
+        Message::Hello {
+            version: PROTOCOL_VERSION,
+        } => {}
+        Message::Hello { version } => {
+            let error = version_mismatch("client", version);
+            write_message(&mut socket, &Message::Error(error.clone())).await?;
+            return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+        }
+        message => return unexpected(&mut socket, message).await,
+    }
 
-    let ser_choices = ron::ser::to_string(&choices).unwrap() + "\n";
-    socket_wr.write_all(ser_choices.as_bytes()).await?;
+    let election_info = election_info_rx.borrow().clone();
+    let choices = election_info.get_voting_system().clone();
+    write_message(&mut socket, &Message::ElectionInfo(election_info)).await?;
 
     // begin accept ballot
-    end_accept_voter_rx.changed().await.unwrap();
-    socket_wr.write_all("\n".as_bytes()).await?;
+    end_accept_voter_rx
+        .changed()
+        .await
+        .map_err(io::Error::other)?;
+    write_message(&mut socket, &Message::VotingOpen).await?;
+
+    // A rejected ballot can be sent again until voting ends
+    let mut voting = true;
+    while voting {
+        tokio::select! {
+            frame = read_frame(&mut socket) => {
+                let reply = match decode(&frame?) {
+                    Ok(Message::Ballot { voter, ballot }) => {
+                        match choices.check_ballot(&ballot) {
+                            Ok(()) => {
+                                let (reply_tx, reply_rx) = oneshot::channel();
+                                if ballots_tx.send((voter, ballot, reply_tx)).await.is_err() {
+                                    // Voting has ended
+                                    break;
+                                }
+                                match reply_rx.await {
+                                    Ok(Ok(())) => {
+                                        voting = false;
+                                        Message::BallotAccepted
+                                    }
+                                    Ok(Err(reason)) => Message::BallotRejected(reason),
+                                    Err(_) => break,
+                                }
+                            }
+                            Err(e) => Message::BallotRejected(e.to_string()),
+                        }
+                    }
+                    Ok(message) => return unexpected(&mut socket, message).await,
+                    Err(e) => Message::BallotRejected(format!("Malformed ballot: {}", e)),
+                };
+                write_message(&mut socket, &reply).await?;
+            }
+            _ = result_rx.changed() => return send_result(&mut socket, &result_rx).await,
+        }
+    }
 
-    let mut reader = BufReader::new(socket_rd);
-    let mut ballot = String::new();
-    reader.read_line(&mut ballot).await?;
+    // wait the result
+    result_rx.changed().await.map_err(io::Error::other)?;
+    send_result(&mut socket, &result_rx).await
+}
 
-    let ballot = ron::de::from_str(&ballot).unwrap();
+/// Send the result of the election to the client
+async fn send_result(
+    socket: &mut TcpStream,
+    result_rx: &watch::Receiver<Option<ElectionResult>>,
+) -> io::Result<()> {
+    let result = result_rx.borrow().clone();
+    let message = match result {
+        Some(result) => Message::Result(result),
+        None => Message::Error("The election has no result".to_string()),
+    };
+    write_message(socket, &message).await
+}
 
-    ballots_tx.send(ballot).await.unwrap();
+/// Tell the client that its message was not expected and end the exchange
+async fn unexpected(socket: &mut TcpStream, message: Message) -> io::Result<()> {
+    let error = format!("Unexpected message {}", message.name());
+    write_message(socket, &Message::Error(error.clone())).await?;
 
-    // wait the result
-    result_rx.changed().await.unwrap();
+    Err(io::Error::new(io::ErrorKind::InvalidData, error))
+}
 
-    let r = result_rx.borrow().clone() + "\n";
-    socket_wr.write_all(r.as_bytes()).await?;
+/// Find the number of votes carried by the ballot of a voter
+fn voter_weight(
+    voter_roll: Option<&VoterRoll>,
+    voted: &HashSet<String>,
+    voter: &Option<VoterCredentials>,
+) -> Result<u32, String> {
+    match (voter_roll, voter) {
+        // Without a voter roll, every ballot carries one vote
+        (None, _) => Ok(1),
+        (Some(roll), Some(voter)) => {
+            let name = voter.get_name();
+            let weight = roll
+                .get_weight(name)
+                .ok_or(format!("{} is not on the voter roll", name))?;
 
-    Ok(())
+            if !roll.check_token(name, voter.get_token()) {
+                Err(format!("Wrong token for {}", name))
+            } else if voted.contains(name) {
+                Err(format!("{} has already voted", name))
+            } else {
+                Ok(weight)
+            }
+        }
+        (Some(_), None) => Err("Anonymous ballots are not allowed with a voter roll".to_string()),
+    }
 }
 
 pub struct VotallyServer {
     end_accept_voter_tx: watch::Sender<()>,
-    vote_handle: Option<JoinHandle<String>>,
+    vote_handle: Option<JoinHandle<(ElectionResult, RawBallots)>>,
     end_accept_ballot_tx: Option<oneshot::Sender<()>>,
-    vote_result: Option<String>,
-    result_tx: watch::Sender<String>,
+    vote_result: Option<ElectionResult>,
+    vote_ballots: Option<RawBallots>,
+    result_tx: watch::Sender<Option<ElectionResult>>,
+    voter_roll_tx: watch::Sender<Option<VoterRoll>>,
+    election_info_tx: watch::Sender<ElectionInfo>,
+    announcement_tx: watch::Sender<Announcement>,
+    discoverable: bool,
+    started: bool,
+    address: SocketAddr,
 }
 
 impl VotallyServer {
-    pub const PORT: &str = "50001";
+    pub const PORT: u16 = 50001;
+
+    /// Longest wait for the clients to receive the result,
+    /// so that a client which stopped answering doesn't hold the server
+    pub const RESULT_TIMEOUT: Duration = Duration::from_secs(10);
 
-    /// Create a new VotallyServer
+    /// Create a new VotallyServer listening on `address`, like `(ip, VotallyServer::PORT)`
+    /// or `"[::]:50001"` to listen on every interface
+    /// `parameters` holds the tie-breaking policy and the number of seats to fill
     /// Initialise process accepting client's connection
-    pub async fn build(
-        address: String,
+    pub async fn build<A: ToSocketAddrs>(
+        address: A,
         name_vote: String,
-        choices: &Vec<&str>,
-    ) -> Result<Self, UnknownVotingSystem> {
-        correct_voting_system(&name_vote)?;
+        choices: &[&str],
+        parameters: VotingParameters,
+    ) -> Result<Self, Error> {
+        let mut vote = find_voting_system(&name_vote[..], choices, &parameters)?;
+        let election_info = ElectionInfo::new("Votally election", vote.get_minimal_info());
+
+        let listener_tcp = TcpListener::bind(address).await.map_err(Error::Bind)?;
+        let address = listener_tcp.local_addr().map_err(Error::Bind)?;
+
+        // Discovery is only a convenience, voters can still type the address of the server,
+        // and clients on the local network can't reach a server listening on loopback
+        let discovery_socket = if address.ip().is_loopback() {
+            None
+        } else {
+            discovery_socket(address, DISCOVERY_PORT).ok()
+        };
+        let discoverable = discovery_socket.is_some();
 
         let (end_accept_voter_tx, mut end_accept_voter_rx) = watch::channel(());
         let (ballots_tx, mut ballots_rx) = mpsc::channel(100);
         let (end_accept_ballot_tx, end_accept_ballot_rx) = oneshot::channel();
-        let (result_tx, result_rx) = watch::channel(String::new());
+        let (result_tx, result_rx) = watch::channel(None);
+        let (voter_roll_tx, voter_roll_rx) = watch::channel::<Option<VoterRoll>>(None);
+        let (election_info_tx, election_info_rx) = watch::channel(election_info);
+        let (announcement_tx, announcement_rx) = watch::channel(Announcement {
+            title: "Votally election".to_string(),
+            method: name_vote.clone(),
+            host: address.ip().to_string(),
+            port: address.port(),
+        });
 
-        let response_info = find_voting_system(&name_vote[..], choices)?.get_minimal_info();
+        // answer clients looking for servers
+        if let Some(socket) = discovery_socket {
+            tokio::spawn(answer_discovery(
+                socket,
+                announcement_rx,
+                end_accept_voter_rx.clone(),
+            ));
+        }
 
         // accept voter
         tokio::spawn(async move {
-            let listener_tcp = TcpListener::bind(address + ":" + Self::PORT).await.unwrap();
-
             let end_rx_clone = end_accept_voter_rx.clone();
             tokio::select! {
             _ = async {
@@ -86,7 +234,7 @@ impl VotallyServer {
                             socket,
                             end_rx_clone.clone(),
                             ballots_tx.clone(),
-                            response_info.clone(),
+                            election_info_rx.clone(),
                             result_rx.clone()
                         ))
                     );
@@ -96,38 +244,31 @@ impl VotallyServer {
             }
         });
 
-        let choices_string: Vec<String> = choices.iter().map(|s| s.to_string()).collect();
-
         // make the poll
         let vote_handle = tokio::spawn(async move {
-            let choices_str: Vec<&str> = choices_string.iter().map(|s| s.as_str()).collect();
-
-            let mut vote = find_voting_system(&name_vote[..], &choices_str).unwrap();
+            let mut voted: HashSet<String> = HashSet::new();
 
             tokio::select! {
             _ = async {
-                loop {
-                    match ballots_rx.recv().await {
-                        Some(message_vote) => {
-
-                            // remove the newline at the end
-                            // let mut message_vote = message_vote.chars();
-                            // message_vote.next_back();
-                            // let message_vote = message_vote.as_str();
-
-                            vote.vote(message_vote)
-                                .unwrap_or_else(|err| eprintln!("{}", err));
-                        },
-                        None => {
-                            break
-                        }
+                while let Some((voter, message_vote, reply_tx)) = ballots_rx.recv().await {
+                    let outcome = voter_weight(voter_roll_rx.borrow().as_ref(), &voted, &voter)
+                        .and_then(|weight| {
+                            vote.vote_weighted(WeightedBallot::new(message_vote, weight))
+                                .map_err(|err| err.to_string())
+                        });
+
+                    if outcome.is_ok()
+                        && let Some(voter) = voter
+                    {
+                        voted.insert(voter.get_name().to_owned());
                     }
+                    let _ = reply_tx.send(outcome);
                 }
             } => {},
             _ = end_accept_ballot_rx => {}
             };
 
-            vote.result()
+            (vote.result(), vote.get_raw_ballots().clone())
         });
 
         Ok(VotallyServer {
@@ -135,40 +276,214 @@ impl VotallyServer {
             vote_handle: Some(vote_handle),
             end_accept_ballot_tx: Some(end_accept_ballot_tx),
             vote_result: None,
+            vote_ballots: None,
             result_tx,
+            voter_roll_tx,
+            election_info_tx,
+            announcement_tx,
+            discoverable,
+            started: false,
+            address,
         })
     }
 
+    /// Give each voter the number of votes written in the voter roll,
+    /// ballots of voters missing from the roll are rejected
+    pub fn with_voter_roll(self, voter_roll: VoterRoll) -> Self {
+        self.voter_roll_tx.send_replace(Some(voter_roll));
+        self.election_info_tx
+            .send_modify(|info| info.set_voter_roll(true));
+        self
+    }
+
+    /// Create a new VotallyServer holding the election of a definition,
+    /// presented to the voters with its title, its description and its candidates
+    /// The definition is checked first, as one built in code wasn't validated when parsed
+    pub async fn from_definition<A: ToSocketAddrs>(
+        address: A,
+        definition: &ElectionDefinition,
+    ) -> Result<Self, Error> {
+        definition.validate()?;
+
+        let server = Self::build(
+            address,
+            definition.get_method().to_string(),
+            &definition.get_choices(),
+            definition.get_parameters().clone(),
+        )
+        .await?
+        .with_title(definition.get_title())
+        .with_description(definition.get_description())
+        .with_candidates(definition.get_candidates());
+
+        Ok(match definition.get_voter_roll() {
+            Some(voter_roll) => server.with_voter_roll(voter_roll.clone()),
+            None => server,
+        })
+    }
+
+    /// Give the title of the election, shown to the voters and to the clients looking for servers
+    pub fn with_title(self, title: &str) -> Self {
+        self.election_info_tx
+            .send_modify(|info| info.set_title(title));
+        self.announcement_tx
+            .send_modify(|announcement| announcement.title = title.to_owned());
+        self
+    }
+
+    /// Give what the election is about, shown to the voters
+    pub fn with_description(self, description: &str) -> Self {
+        self.election_info_tx
+            .send_modify(|info| info.set_description(description));
+        self
+    }
+
+    /// Give the presentation of the candidates shown to the voters
+    pub fn with_candidates(self, candidates: &[Candidate]) -> Self {
+        self.election_info_tx
+            .send_modify(|info| info.set_candidates(candidates));
+        self
+    }
+
+    /// Get the address the server listens on
+    pub fn get_address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Check if clients on the local network can find the server,
+    /// which they can't if it listens on loopback or the discovery port can't be opened
+    pub fn is_discoverable(&self) -> bool {
+        self.discoverable
+    }
+
     /// End accepting new connection and start the poll
-    pub async fn start_ballot(&self) -> Result<(), watch::error::SendError<()>> {
-        self.end_accept_voter_tx.send(())
+    pub async fn start_ballot(&mut self) -> Result<(), Error> {
+        if self.started {
+            return Err(Error::VotingStarted);
+        }
+        self.started = true;
+
+        // Without any client, nobody is waiting for voting to start
+        self.end_accept_voter_tx.send_replace(());
+        Ok(())
     }
 
     /// End the poll
     pub async fn end_poll(&mut self) {
-        match self.end_accept_ballot_tx.take() {
-            Some(s) => {
-                let _ = s.send(()); // return Err if ballots_rx is yet closed
-                self.end_accept_ballot_tx = None;
-            }
-            None => {}
+        if let Some(s) = self.end_accept_ballot_tx.take() {
+            let _ = s.send(()); // return Err if ballots_rx is yet closed
+            self.end_accept_ballot_tx = None;
         }
     }
 
-    pub async fn calculate_result(&mut self) {
-        match self.vote_handle.take() {
-            Some(v) => self.vote_result = v.await.ok(),
-            None => {}
+    /// Count the ballots and send the result to every client
+    pub async fn calculate_result(&mut self) -> Result<(), Error> {
+        if let Some(v) = self.vote_handle.take() {
+            let (result, ballots) = v.await.map_err(Error::Count)?;
+            self.vote_result = Some(result);
+            self.vote_ballots = Some(ballots);
         }
-        self.vote_handle = None;
 
-        self.result_tx
-            .send(self.vote_result.clone().unwrap())
-            .unwrap();
-        self.result_tx.closed().await;
+        // Without any client left, nobody is waiting for the result
+        self.result_tx.send_replace(self.vote_result.clone());
+        let _ = tokio::time::timeout(Self::RESULT_TIMEOUT, self.result_tx.closed()).await;
+        Ok(())
+    }
+
+    /// Get the result of the election, None before calculate_result
+    pub fn result(&self) -> Option<&ElectionResult> {
+        self.vote_result.as_ref()
+    }
+
+    /// Get every accepted ballot, to count them again with another voting system,
+    /// None before calculate_result
+    pub fn raw_ballots(&self) -> Option<&RawBallots> {
+        self.vote_ballots.as_ref()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn voter_weight_with_roll() {
+        let roll = VoterRoll::new().with_voter("Alice", 3, "k7Fq2");
+        let alice = Some(VoterCredentials::new("Alice", "k7Fq2"));
+        let mut voted = HashSet::new();
+
+        assert_eq!(Ok(1), voter_weight(None, &voted, &None));
+        assert_eq!(Ok(3), voter_weight(Some(&roll), &voted, &alice));
+        assert!(
+            voter_weight(
+                Some(&roll),
+                &voted,
+                &Some(VoterCredentials::new("Bob", "k7Fq2"))
+            )
+            .is_err()
+        );
+        assert!(voter_weight(Some(&roll), &voted, &None).is_err());
+
+        // Someone knowing the name of a voter can't vote in their place
+        assert_eq!(
+            Err("Wrong token for Alice".to_string()),
+            voter_weight(
+                Some(&roll),
+                &voted,
+                &Some(VoterCredentials::new("Alice", "guess"))
+            )
+        );
+
+        voted.insert("Alice".to_string());
+        assert!(voter_weight(Some(&roll), &voted, &alice).is_err());
+    }
+
+    #[tokio::test]
+    async fn voter_roll_is_advertised() {
+        let server = VotallyServer::build(
+            "127.0.0.1:0",
+            "plurality".to_string(),
+            &["A", "B"],
+            VotingParameters::default(),
+        )
+        .await
+        .unwrap();
+        assert!(!server.election_info_tx.borrow().has_voter_roll());
+
+        // Clients only ask for a name and a token when the election has a voter roll
+        let server = server.with_voter_roll(VoterRoll::new().with_voter("Alice", 1, "k7Fq2"));
+        assert!(server.election_info_tx.borrow().has_voter_roll());
+    }
+
+    #[tokio::test]
+    async fn start_twice() {
+        let mut server = VotallyServer::build(
+            "127.0.0.1:0",
+            "plurality".to_string(),
+            &["A", "B"],
+            VotingParameters::default(),
+        )
+        .await
+        .unwrap();
+
+        // Starting doesn't depend on clients being connected
+        assert!(server.start_ballot().await.is_ok());
+        assert!(matches!(
+            server.start_ballot().await,
+            Err(Error::VotingStarted)
+        ));
+    }
+
+    #[tokio::test]
+    async fn invalid_definition() {
+        let candidates = vec![Candidate::new("A", ""), Candidate::new("B", "")];
+        let definition = ElectionDefinition::new("Treasurer", candidates, "plurality")
+            .with_voter_roll(VoterRoll::new());
 
-    pub fn result(&self) -> String {
-        self.vote_result.clone().unwrap()
+        // An empty voter roll would reject every ballot
+        assert!(matches!(
+            VotallyServer::from_definition("127.0.0.1:0", &definition).await,
+            Err(Error::InvalidElection(_))
+        ));
     }
 }
@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Voters allowed to vote, the number of votes each of them carries,
+/// for instance the number of mandates of each delegate,
+/// and the token each of them gives to prove who they are
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::network::VoterRoll;
+///
+/// let roll: VoterRoll = "Alice,3,k7Fq2\nBob,x9Zt4".parse().unwrap();
+///
+/// assert_eq!(Some(3), roll.get_weight("Alice"));
+/// assert_eq!(Some(1), roll.get_weight("Bob"));
+/// assert_eq!(None, roll.get_weight("Eve"));
+/// assert!(roll.check_token("Bob", "x9Zt4"));
+/// assert!(!roll.check_token("Bob", "k7Fq2"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VoterRoll(HashMap<String, Voter>);
+
+/// Entry of a voter in the voter roll
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Voter {
+    #[serde(default = "one")]
+    weight: u32,
+    token: String,
+}
+
+/// Weight of a voter when it is left out
+fn one() -> u32 {
+    1
+}
+
+impl VoterRoll {
+    /// Create an empty voter roll
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Add a voter carrying weight votes and proving who they are with token
+    pub fn with_voter(mut self, name: &str, weight: u32, token: &str) -> Self {
+        self.0.insert(
+            name.to_owned(),
+            Voter {
+                weight,
+                token: token.to_owned(),
+            },
+        );
+        self
+    }
+
+    /// Get the number of votes carried by a voter, None if the voter is not on the roll
+    pub fn get_weight(&self, name: &str) -> Option<u32> {
+        self.0.get(name).map(|voter| voter.weight)
+    }
+
+    /// Check that token is the one of the voter
+    pub fn check_token(&self, name: &str, token: &str) -> bool {
+        self.0.get(name).is_some_and(|voter| {
+            // Compare every byte so the time taken doesn't tell how much of the token is right
+            voter.token.len() == token.len()
+                && voter
+                    .token
+                    .bytes()
+                    .zip(token.bytes())
+                    .fold(0, |diff, (a, b)| diff | (a ^ b))
+                    == 0
+        })
+    }
+
+    /// Get every voter and the number of votes they carry
+    pub fn voters(&self) -> impl Iterator<Item = (&String, u32)> {
+        self.0.iter().map(|(name, voter)| (name, voter.weight))
+    }
+
+    /// Get the voters without a token
+    pub fn voters_without_token(&self) -> impl Iterator<Item = &String> {
+        self.0
+            .iter()
+            .filter(|(_, voter)| voter.token.is_empty())
+            .map(|(name, _)| name)
+    }
+
+    /// Get the number of voters
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Return true if there is no voter
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for VoterRoll {
+    type Err = InvalidVoterRoll;
+
+    /// Parse one voter per line written `<name>,<weight>,<token>`,
+    /// or `<name>,<token>` for a voter carrying one vote
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut roll = VoterRoll::new();
+
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            let invalid = || InvalidVoterRoll(line.to_string());
+            let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+
+            let (name, weight, token) = match fields[..] {
+                [name, token] => (name, 1, token),
+                [name, weight, token] => (
+                    name,
+                    weight.parse().ok().filter(|w| *w > 0).ok_or_else(invalid)?,
+                    token,
+                ),
+                _ => return Err(invalid()),
+            };
+
+            if name.is_empty() || token.is_empty() || roll.get_weight(name).is_some() {
+                return Err(invalid());
+            }
+            roll = roll.with_voter(name, weight, token);
+        }
+
+        Ok(roll)
+    }
+}
+
+/// Name of a voter on the voter roll and the token proving it is them
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VoterCredentials {
+    name: String,
+    token: String,
+}
+
+impl VoterCredentials {
+    pub fn new(name: &str, token: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            token: token.to_owned(),
+        }
+    }
+
+    /// Get the name of the voter on the voter roll
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the token of the voter
+    pub fn get_token(&self) -> &str {
+        &self.token
+    }
+}
+
+/// Error for invalid voter roll
+#[derive(Debug)]
+pub struct InvalidVoterRoll(String);
+
+impl fmt::Display for InvalidVoterRoll {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid voter roll line: {}", self.0)
+    }
+}
+
+impl Error for InvalidVoterRoll {}
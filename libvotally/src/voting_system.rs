@@ -3,9 +3,22 @@ use std::fmt;
 
 mod definition;
 
-use crate::voting_system::definition::InvalidBallot;
+pub use self::definition::{
+    BallotForm, InvalidBallot, InvalidParameters, InvalidScoreRange, MinimalVotingSystemInfo,
+    RawBallots, ScoreRange, SingleBallot, VotingParameters, VotingSystem, WeightedBallot,
+};
 
-pub use self::definition::{BallotForm, MinimalVotingSystemInfo, SingleBallot, VotingSystem};
+mod result;
+pub use self::result::{ElectionResult, PairwiseVictory, ResultDetails};
+
+mod explanation;
+pub use self::explanation::CountStep;
+
+mod pairwise;
+pub use self::pairwise::PairwiseMatrix;
+
+mod tie_break;
+pub use self::tie_break::{TieBreak, UnknownTieBreak};
 
 mod plurality;
 pub use self::plurality::Plurality;
@@ -19,6 +32,41 @@ pub use self::borda_count::BordaCount;
 mod black_method;
 pub use self::black_method::BlackMethod;
 
+mod instant_runoff;
+pub use self::instant_runoff::InstantRunoff;
+
+mod schulze;
+pub use self::schulze::Schulze;
+
+mod ranked_pairs;
+pub use self::ranked_pairs::RankedPairs;
+
+mod copeland;
+pub use self::copeland::Copeland;
+
+mod minimax;
+pub use self::minimax::Minimax;
+
+mod single_transferable_vote;
+pub use self::single_transferable_vote::SingleTransferableVote;
+
+mod score_voting;
+pub use self::score_voting::ScoreVoting;
+
+mod star_voting;
+pub use self::star_voting::StarVoting;
+
+mod majority_judgment;
+pub use self::majority_judgment::MajorityJudgment;
+
+mod registry;
+pub use self::registry::{
+    Election, Registry, VotingSystemFactory, register_factory, register_voting_system, registry,
+};
+
+mod compare;
+pub use self::compare::{Comparison, compare};
+
 /// Error for unknown voting system
 #[derive(Debug)]
 pub struct UnknownVotingSystem(String);
@@ -31,71 +79,91 @@ impl fmt::Display for UnknownVotingSystem {
 
 impl Error for UnknownVotingSystem {}
 
-pub enum VotingSystemEnum {
-    Plurality(Plurality),
-    Approval(Approval),
-    Borda(BordaCount),
-    Black(BlackMethod),
+/// Error for ballots which can't be counted again
+#[derive(Debug)]
+pub enum RecountError {
+    /// No voting system of the registry has the given name
+    UnknownVotingSystem(UnknownVotingSystem),
+    /// A stored ballot doesn't suit the voting system
+    InvalidBallot(InvalidBallot),
 }
 
-impl VotingSystemEnum {
-    pub fn result(&self) -> String {
+impl fmt::Display for RecountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            VotingSystemEnum::Plurality(p) => p.result(),
-            VotingSystemEnum::Approval(a) => a.result(),
-            VotingSystemEnum::Borda(b) => b.result(),
-            VotingSystemEnum::Black(b) => b.result(),
+            RecountError::UnknownVotingSystem(e) => write!(f, "{}", e),
+            RecountError::InvalidBallot(e) => write!(f, "{}", e),
         }
     }
+}
 
-    pub fn get_minimal_info(&self) -> MinimalVotingSystemInfo {
-        match self {
-            VotingSystemEnum::Plurality(p) => p.get_minimal_info(),
-            VotingSystemEnum::Approval(a) => a.get_minimal_info(),
-            VotingSystemEnum::Borda(b) => b.get_minimal_info(),
-            VotingSystemEnum::Black(b) => b.get_minimal_info(),
-        }
+impl Error for RecountError {}
+
+impl From<UnknownVotingSystem> for RecountError {
+    fn from(e: UnknownVotingSystem) -> Self {
+        RecountError::UnknownVotingSystem(e)
     }
+}
 
-    pub fn vote(&mut self, ballot: SingleBallot) -> Result<(), InvalidBallot> {
-        match self {
-            VotingSystemEnum::Plurality(p) => p.vote(ballot),
-            VotingSystemEnum::Approval(a) => a.vote(ballot),
-            VotingSystemEnum::Borda(b) => b.vote(ballot),
-            VotingSystemEnum::Black(b) => b.vote(ballot),
-        }
+impl From<InvalidBallot> for RecountError {
+    fn from(e: InvalidBallot) -> Self {
+        RecountError::InvalidBallot(e)
     }
 }
 
-/// Try to find the voting system which is associated to name.
-/// Return a voting system if found
+/// Try to find the voting system which is associated to name in the registry.
+/// Return a new election if found
 /// and return a UnknownVotingSystem error else.
 pub fn find_voting_system(
     name: &str,
-    choices: &Vec<&str>,
-) -> Result<VotingSystemEnum, UnknownVotingSystem> {
-    match name {
-        Plurality::NAME => Ok(VotingSystemEnum::Plurality(Plurality::new(choices))),
-        Approval::NAME => Ok(VotingSystemEnum::Approval(Approval::new(choices))),
-        BordaCount::NAME => Ok(VotingSystemEnum::Borda(BordaCount::new(choices))),
-        BlackMethod::NAME => Ok(VotingSystemEnum::Black(BlackMethod::new(choices))),
-        _ => Err(UnknownVotingSystem(format!("{}", name))),
+    choices: &[&str],
+    parameters: &VotingParameters,
+) -> Result<Box<dyn Election>, UnknownVotingSystem> {
+    // The registry is released before creating the election
+    let factory = registry()
+        .find(name)
+        .ok_or(UnknownVotingSystem(name.to_string()))?;
+    Ok(factory.create(choices, parameters.clone()))
+}
+
+/// Count stored ballots again with the voting system which is associated to name.
+/// Return the result of the election
+/// and return a RecountError if the voting system is unknown or doesn't accept the ballots.
+pub fn recount(
+    name: &str,
+    ballots: &RawBallots,
+    parameters: &VotingParameters,
+) -> Result<ElectionResult, RecountError> {
+    let choices: Vec<&str> = ballots.get_choices().iter().map(|c| c.as_str()).collect();
+    let mut vote = find_voting_system(name, &choices, parameters)?;
+
+    for b in ballots.get_ballots() {
+        vote.vote_weighted(b.clone())?;
+    }
+
+    Ok(vote.result())
+}
+
+/// Return Err(InvalidParameters) if several seats are to be filled
+/// by the voting system which is associated to name and fills a single seat
+pub fn check_seats(name: &str, parameters: &VotingParameters) -> Result<(), InvalidParameters> {
+    let multi_winner = registry().find(name).is_some_and(|f| f.multi_winner());
+
+    if parameters.get_seats() > 1 && !multi_winner {
+        Err(InvalidParameters(format!(
+            "the voting system {} fills a single seat",
+            name
+        )))
+    } else {
+        Ok(())
     }
 }
 
 /// Return Ok(()) if name_vote is known and Err(UnknownVotingSystem) else
-/// Current known voting system: plurality
 pub fn correct_voting_system(name_vote: &str) -> Result<(), UnknownVotingSystem> {
-    if vec![
-        Plurality::NAME,
-        Approval::NAME,
-        BordaCount::NAME,
-        BlackMethod::NAME,
-    ]
-    .contains(&name_vote)
-    {
+    if registry().find(name_vote).is_some() {
         Ok(())
     } else {
-        Err(UnknownVotingSystem(format!("{}", name_vote)))
+        Err(UnknownVotingSystem(name_vote.to_string()))
     }
 }
@@ -1,3 +1,4 @@
+use crate::voting_system::ElectionResult;
 use crate::voting_system::definition::*;
 
 /// # Approval voting
@@ -6,7 +7,7 @@ use crate::voting_system::definition::*;
 /// ```rust
 /// use libvotally::voting_system::{Approval, VotingSystem, SingleBallot};
 ///
-/// let mut p = Approval::new(&vec!["A", "B", "C"]);
+/// let mut p = Approval::new(&["A", "B", "C"]);
 ///
 /// p.vote(SingleBallot::Approved(vec![
 ///     "A".to_string(),
@@ -14,7 +15,7 @@ use crate::voting_system::definition::*;
 /// ])).unwrap();
 /// p.vote(SingleBallot::Approved(vec!["B".to_string()])).unwrap();
 ///
-/// assert_eq!("B", p.result());
+/// assert_eq!(vec!["B".to_string()], p.result().get_winners());
 /// ```
 pub struct Approval(VotingSystemInfo<PointBallots>);
 
@@ -24,11 +25,12 @@ impl VotingSystem for Approval {
     const NAME: &str = "approval";
     const LONG_NAME: &str = "Approval voting";
 
-    fn new(choices: &Vec<&str>) -> Self {
+    fn with_parameters(choices: &[&str], parameters: VotingParameters) -> Self {
         Self(VotingSystemInfo::new(
             Self::LONG_NAME,
             BallotForm::Approved,
             choices,
+            parameters,
         ))
     }
 
@@ -40,13 +42,14 @@ impl VotingSystem for Approval {
         &mut self.0
     }
 
-    fn result(&self) -> String {
+    fn result(&self) -> ElectionResult {
         let PointBallots(c) = self.0.get_ballot_box();
-        c.iter()
-            .max_by(|a, b| a.1.cmp(&b.1))
-            .map(|(k, _v)| k)
-            .cloned()
-            .unwrap()
+        ElectionResult::from_scores(
+            Self::LONG_NAME,
+            c.iter().map(|(k, v)| (k.to_owned(), *v as f64)).collect(),
+            self.0.get_count(),
+        )
+        .break_ties(self.0.get_parameters().get_tie_break(), &[])
     }
 }
 
@@ -56,12 +59,12 @@ mod tests {
 
     #[test]
     fn approval_voting() {
-        let mut p = Approval::new(&vec!["A", "B", "C"]);
+        let mut p = Approval::new(&["A", "B", "C"]);
 
-        for v in vec!["A", "B", "A", "C", "B", "A"] {
+        for v in ["A", "B", "A", "C", "B", "A"] {
             p.vote(SingleBallot::Approved(vec![v.to_string()])).unwrap();
         }
 
-        assert_eq!("A", p.result());
+        assert_eq!(vec!["A".to_string()], p.result().get_winners());
     }
 }
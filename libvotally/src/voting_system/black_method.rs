@@ -1,7 +1,12 @@
+use std::collections::HashMap;
+
 use crate::voting_system::definition::*;
+use crate::voting_system::explanation::condorcet_step;
+use crate::voting_system::result::{move_first, rank_by_scores};
+use crate::voting_system::{CountStep, ElectionResult, PairwiseMatrix, ResultDetails};
 
 impl Ballots for (PointBallots, BattleBallots) {
-    fn new(choices: &Vec<&str>) -> Self {
+    fn new(choices: &[&str]) -> Self {
         (PointBallots::new(choices), BattleBallots::new(choices))
     }
 
@@ -10,10 +15,10 @@ impl Ballots for (PointBallots, BattleBallots) {
         p.choices()
     }
 
-    fn vote(&mut self, ballot: SingleBallot) -> Result<(), InvalidBallot> {
+    fn vote_weighted(&mut self, ballot: WeightedBallot) -> Result<(), InvalidBallot> {
         let (p, b) = self;
-        p.vote(ballot.clone())?;
-        b.vote(ballot)
+        p.vote_weighted(ballot.clone())?;
+        b.vote_weighted(ballot)
     }
 }
 
@@ -26,7 +31,7 @@ impl Ballots for (PointBallots, BattleBallots) {
 /// ```rust
 /// use libvotally::voting_system::{BlackMethod, VotingSystem, SingleBallot};
 ///
-/// let mut b = BlackMethod::new(&vec!["A", "B", "C"]);
+/// let mut b = BlackMethod::new(&["A", "B", "C"]);
 ///
 /// b.vote(SingleBallot::Ranked(vec![
 ///     "A".to_string(),
@@ -39,7 +44,7 @@ impl Ballots for (PointBallots, BattleBallots) {
 ///     "B".to_string(),
 /// ])).unwrap();
 ///
-/// assert_eq!("A", b.result());
+/// assert_eq!(vec!["A".to_string()], b.result().get_winners());
 /// ```
 ///
 /// [Black_wikipedia]: https://en.wikipedia.org/wiki/Black's_method
@@ -51,11 +56,12 @@ impl VotingSystem for BlackMethod {
     const NAME: &str = "black";
     const LONG_NAME: &str = "Black's method";
 
-    fn new(choices: &Vec<&str>) -> Self {
+    fn with_parameters(choices: &[&str], parameters: VotingParameters) -> Self {
         Self(VotingSystemInfo::new(
             Self::LONG_NAME,
             BallotForm::Ranked,
             choices,
+            parameters,
         ))
     }
 
@@ -67,34 +73,29 @@ impl VotingSystem for BlackMethod {
         &mut self.0
     }
 
-    fn result(&self) -> String {
-        let (PointBallots(p), BattleBallots(b)) = self.0.get_ballot_box();
-        let ch = p.keys();
+    fn result(&self) -> ElectionResult {
+        let (PointBallots(p), b) = self.0.get_ballot_box();
+
+        let scores: HashMap<String, f64> =
+            p.iter().map(|(k, v)| (k.to_owned(), *v as f64)).collect();
+        let mut ranking = rank_by_scores(&scores);
+        let matrix = PairwiseMatrix::from(b);
 
         // Condorcet winner ?
-        match ch
-            .clone()
-            .filter(|c1| {
-                ch.clone().all(|c2| {
-                    (c1.to_string() == c2.to_string())
-                        || (*b.get(&(c1.to_string(), c2.to_string())).unwrap()
-                            > *b.get(&(c2.to_string(), c1.to_string())).unwrap())
-                })
-            })
-            .next()
-        {
-            Some(c) => c.to_string(),
-            None =>
-            // Borda count
-            {
-                println!("Borda");
-                p.iter()
-                    .min_by(|a, b| a.1.cmp(&b.1))
-                    .map(|(k, _v)| k)
-                    .cloned()
-                    .unwrap()
-            }
+        if let Some(c) = b.condorcet_winner() {
+            // The Condorcet winner goes first, others stay ranked by Borda count
+            move_first(&mut ranking, c);
         }
+        let condorcet = condorcet_step(&matrix);
+        let borda = CountStep::Scores {
+            label: "Borda count".to_string(),
+            scores: scores.clone(),
+        };
+
+        ElectionResult::new(Self::LONG_NAME, ranking, scores, self.0.get_count())
+            .with_explanation(vec![condorcet, borda])
+            .break_ties(self.0.get_parameters().get_tie_break(), &[])
+            .with_details(ResultDetails::Pairwise(matrix))
     }
 }
 
@@ -104,9 +105,9 @@ mod tests {
 
     #[test]
     fn black_method() {
-        let mut black1 = BlackMethod::new(&vec!["A", "B", "C"]);
+        let mut black1 = BlackMethod::new(&["A", "B", "C"]);
 
-        for v in vec![
+        for v in [
             vec!["A", "B", "C"],
             vec!["A", "B", "C"],
             vec!["A", "B", "C"],
@@ -120,11 +121,18 @@ mod tests {
                 .unwrap();
         }
 
-        assert_eq!("A", black1.result());
+        let result = black1.result();
+        assert_eq!(vec!["A".to_string()], result.get_winners());
+        match result.get_details() {
+            Some(ResultDetails::Pairwise(matrix)) => {
+                assert_eq!(Some(&"A".to_string()), matrix.condorcet_winner());
+            }
+            _ => panic!("missing pairwise matrix"),
+        }
 
-        let mut black2 = BlackMethod::new(&vec!["A", "B", "C"]);
+        let mut black2 = BlackMethod::new(&["A", "B", "C"]);
 
-        for v in vec![
+        for v in [
             vec!["A", "B", "C"],
             vec!["A", "B", "C"],
             vec!["B", "C", "A"],
@@ -137,6 +145,6 @@ mod tests {
                 .unwrap();
         }
 
-        assert_eq!("B", black2.result());
+        assert_eq!(vec!["B".to_string()], black2.result().get_winners());
     }
 }
@@ -1,12 +1,17 @@
+use crate::voting_system::ElectionResult;
 use crate::voting_system::definition::*;
 
 /// # Borda count
 ///
+/// Each ballot gives a candidate one point for each candidate ranked below it,
+/// so the last of n candidates gets 0 points and the first n - 1,
+/// and the candidate with the most points wins.
+///
 /// Here an exemple :
 /// ```rust
 /// use libvotally::voting_system::{BordaCount, VotingSystem, SingleBallot};
 ///
-/// let mut p = BordaCount::new(&vec!["A", "B", "C"]);
+/// let mut p = BordaCount::new(&["A", "B", "C"]);
 ///
 /// p.vote(SingleBallot::Ranked(vec![
 ///     "A".to_string(),
@@ -19,7 +24,7 @@ use crate::voting_system::definition::*;
 ///     "B".to_string(),
 /// ])).unwrap();
 ///
-/// assert_eq!("A", p.result());
+/// assert_eq!(vec!["A".to_string()], p.result().get_winners());
 /// ```
 pub struct BordaCount(VotingSystemInfo<PointBallots>);
 
@@ -29,11 +34,12 @@ impl VotingSystem for BordaCount {
     const NAME: &str = "borda";
     const LONG_NAME: &str = "Borda count";
 
-    fn new(choices: &Vec<&str>) -> Self {
+    fn with_parameters(choices: &[&str], parameters: VotingParameters) -> Self {
         Self(VotingSystemInfo::new(
             Self::LONG_NAME,
             BallotForm::Ranked,
             choices,
+            parameters,
         ))
     }
 
@@ -45,13 +51,14 @@ impl VotingSystem for BordaCount {
         &mut self.0
     }
 
-    fn result(&self) -> String {
+    fn result(&self) -> ElectionResult {
         let PointBallots(c) = self.0.get_ballot_box();
-        c.iter()
-            .min_by(|a, b| a.1.cmp(&b.1))
-            .map(|(k, _v)| k)
-            .cloned()
-            .unwrap()
+        ElectionResult::from_scores(
+            Self::LONG_NAME,
+            c.iter().map(|(k, v)| (k.to_owned(), *v as f64)).collect(),
+            self.0.get_count(),
+        )
+        .break_ties(self.0.get_parameters().get_tie_break(), &[])
     }
 }
 
@@ -61,15 +68,32 @@ mod tests {
 
     #[test]
     fn borda_voting() {
-        let mut p = BordaCount::new(&vec!["A", "B", "C"]);
+        let mut p = BordaCount::new(&["A", "B", "C"]);
+
+        for v in [vec!["A", "B", "C"], vec!["C", "A", "B"]] {
+            p.vote(SingleBallot::Ranked(
+                v.into_iter().map(|s| s.to_string()).collect(),
+            ))
+            .unwrap();
+        }
+
+        assert_eq!(vec!["A".to_string()], p.result().get_winners());
+    }
+
+    #[test]
+    fn borda_points() {
+        let mut p = BordaCount::new(&["A", "B", "C"]);
 
-        for v in vec![vec!["A", "B", "C"], vec!["C", "A", "B"]] {
+        for v in [vec!["A", "B", "C"], vec!["C", "A", "B"]] {
             p.vote(SingleBallot::Ranked(
                 v.into_iter().map(|s| s.to_string()).collect(),
             ))
             .unwrap();
         }
 
-        assert_eq!("A", p.result());
+        let result = p.result();
+        assert_eq!(Some(3.0), result.get_score("A"));
+        assert_eq!(Some(1.0), result.get_score("B"));
+        assert_eq!(Some(2.0), result.get_score("C"));
     }
 }
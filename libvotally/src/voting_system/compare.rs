@@ -0,0 +1,239 @@
+use std::fmt;
+
+use crate::voting_system::{
+    ElectionResult, RawBallots, SingleBallot, VotingParameters, WeightedBallot, registry,
+};
+
+/// Results of several voting systems on the same ballots
+pub struct Comparison {
+    /// Result of each voting system accepting the ballots
+    results: Vec<ElectionResult>,
+    /// Names of the voting systems which can't count the ballots
+    incompatible: Vec<String>,
+}
+
+impl Comparison {
+    /// Get the result of each voting system accepting the ballots
+    pub fn get_results(&self) -> &[ElectionResult] {
+        &self.results
+    }
+
+    /// Get the names of the voting systems which can't count the ballots
+    pub fn get_incompatible(&self) -> &[String] {
+        &self.incompatible
+    }
+}
+
+/// Count the same ballots with every known voting system.
+/// Ballots are converted to the form of each voting system when possible,
+/// for instance ranked ballots also give uninominal ballots for plurality.
+/// Equal scores or grades give equal rankings, which ranked voting systems then accept.
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::voting_system::{compare, RawBallots, SingleBallot, VotingParameters};
+///
+/// let ballots: Vec<SingleBallot> = ["ABC", "ABC", "BCA", "BCA", "CBA"]
+///     .iter()
+///     .map(|v| SingleBallot::Ranked(v.chars().map(|c| c.to_string()).collect()))
+///     .collect();
+///
+/// let comparison = compare(
+///     &RawBallots::from_ballots(&["A", "B", "C"], ballots),
+///     &VotingParameters::default(),
+/// );
+///
+/// // Plurality, Borda count, Black's method, IRV...
+/// assert!(comparison.get_results().len() > 5);
+/// assert!(comparison.get_incompatible().contains(&"approval".to_string()));
+/// ```
+pub fn compare(ballots: &RawBallots, parameters: &VotingParameters) -> Comparison {
+    let choices: Vec<&str> = ballots.get_choices().iter().map(|c| c.as_str()).collect();
+    let mut results = Vec::new();
+    let mut incompatible = Vec::new();
+
+    // The registry is released before creating the elections
+    let names: Vec<&str> = registry().factories().map(|f| f.name()).collect();
+    let factories = names.into_iter().filter_map(|name| registry().find(name));
+    for factory in factories {
+        let mut vote = factory.create(&choices, parameters.clone());
+        let form = vote.get_minimal_info().get_ballot_form();
+        let converted: Option<Vec<WeightedBallot>> = ballots
+            .get_ballots()
+            .iter()
+            .map(|b| {
+                b.get_ballot()
+                    .convert(form)
+                    .map(|converted| WeightedBallot::new(converted, b.get_weight()))
+            })
+            .collect();
+
+        // Equal rankings coming from equal scores or grades are not a choice of the voters
+        let tied = ballots
+            .get_ballots()
+            .iter()
+            .zip(converted.iter().flatten())
+            .any(|(b, c)| {
+                !matches!(b.get_ballot(), SingleBallot::RankedWithTies(_))
+                    && matches!(c.get_ballot(), SingleBallot::RankedWithTies(_))
+            });
+        if tied {
+            vote = factory.create(&choices, parameters.clone().with_partial_ranking(true));
+        }
+
+        let accepted = converted
+            .is_some_and(|converted| converted.into_iter().all(|b| vote.vote_weighted(b).is_ok()));
+
+        if accepted {
+            results.push(vote.result());
+        } else {
+            incompatible.push(factory.name().to_string());
+        }
+    }
+
+    Comparison {
+        results,
+        incompatible,
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows: Vec<[String; 3]> = self
+            .results
+            .iter()
+            .map(|r| {
+                let winners = if r.get_seats() > 1 {
+                    r.get_elected().join(", ")
+                } else {
+                    r.get_winners().join(" = ")
+                };
+                let ranking: Vec<String> = r
+                    .get_ranking()
+                    .iter()
+                    .map(|rank| rank.join(" = "))
+                    .collect();
+                [
+                    r.get_voting_system().to_owned(),
+                    winners,
+                    ranking.join(" > "),
+                ]
+            })
+            .collect();
+
+        let header = [
+            "Voting system".to_string(),
+            "Winner".to_string(),
+            "Ranking".to_string(),
+        ];
+        let widths: Vec<usize> = (0..2)
+            .map(|i| {
+                rows.iter()
+                    .chain([&header])
+                    .map(|row| row[i].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        for (i, row) in [&header].into_iter().chain(&rows).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(
+                f,
+                "{:w0$} | {:w1$} | {}",
+                row[0],
+                row[1],
+                row[2],
+                w0 = widths[0],
+                w1 = widths[1]
+            )?;
+        }
+
+        if !self.incompatible.is_empty() {
+            write!(
+                f,
+                "\nIncompatible with these ballots: {}",
+                self.incompatible.join(", ")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voting_system::SingleBallot;
+    use std::collections::HashMap;
+
+    #[test]
+    fn compare_scored_ballots() {
+        let ballots: Vec<SingleBallot> = [(5, 4, 0), (5, 4, 0), (0, 4, 5), (0, 5, 1)]
+            .iter()
+            .map(|(a, b, c)| {
+                SingleBallot::Scored(HashMap::from([
+                    ("A".to_string(), *a),
+                    ("B".to_string(), *b),
+                    ("C".to_string(), *c),
+                ]))
+            })
+            .collect();
+
+        let comparison = compare(
+            &RawBallots::from_ballots(&["A", "B", "C"], ballots),
+            &VotingParameters::default(),
+        );
+        let winners: HashMap<&str, Vec<String>> = comparison
+            .get_results()
+            .iter()
+            .map(|r| (r.get_voting_system(), r.get_winners()))
+            .collect();
+
+        // Scored ballots without equal scores are also ranked ballots
+        assert_eq!(vec!["A".to_string()], winners["Plurality voting"]);
+        assert_eq!(vec!["B".to_string()], winners["Borda count"]);
+        assert_eq!(vec!["B".to_string()], winners["Score voting"]);
+        // Other voting systems may be registered, only check the built-in ones
+        let incompatible = comparison.get_incompatible();
+        assert!(incompatible.contains(&"approval".to_string()));
+        assert!(incompatible.contains(&"majority-judgment".to_string()));
+        assert!(!incompatible.contains(&"borda".to_string()));
+    }
+
+    #[test]
+    fn compare_equal_scores() {
+        let ballots: Vec<SingleBallot> = [(5, 5, 0), (5, 4, 0), (0, 5, 4)]
+            .iter()
+            .map(|(a, b, c)| {
+                SingleBallot::Scored(HashMap::from([
+                    ("A".to_string(), *a),
+                    ("B".to_string(), *b),
+                    ("C".to_string(), *c),
+                ]))
+            })
+            .collect();
+
+        let comparison = compare(
+            &RawBallots::from_ballots(&["A", "B", "C"], ballots),
+            &VotingParameters::default(),
+        );
+        let borda = comparison
+            .get_results()
+            .iter()
+            .find(|r| r.get_voting_system() == "Borda count")
+            .unwrap();
+
+        // A and B are ranked equally on the first ballot
+        assert_eq!(Some(3.0), borda.get_score("A"));
+        assert_eq!(vec!["B".to_string()], borda.get_winners());
+        // Plurality can't pick one of them
+        assert!(
+            comparison
+                .get_incompatible()
+                .contains(&"plurality".to_string())
+        );
+    }
+}
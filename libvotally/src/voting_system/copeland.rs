@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use crate::voting_system::definition::*;
+use crate::voting_system::explanation::condorcet_step;
+use crate::voting_system::result::{move_first, rank_by_scores};
+use crate::voting_system::{CountStep, ElectionResult, PairwiseMatrix};
+
+/// # Copeland's method
+/// Each candidate scores its number of pairwise victories minus its number of pairwise defeats.
+/// A Condorcet winner is always elected.
+/// See [Copeland_wikipedia].
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::voting_system::{Copeland, VotingSystem, SingleBallot};
+///
+/// let mut c = Copeland::new(&["A", "B", "C"]);
+///
+/// c.vote(SingleBallot::Ranked(vec![
+///     "A".to_string(),
+///     "B".to_string(),
+///     "C".to_string(),
+/// ])).unwrap();
+/// c.vote(SingleBallot::Ranked(vec![
+///     "C".to_string(),
+///     "A".to_string(),
+///     "B".to_string(),
+/// ])).unwrap();
+///
+/// assert_eq!(vec!["A".to_string()], c.result().get_winners());
+/// ```
+///
+/// [Copeland_wikipedia]: https://en.wikipedia.org/wiki/Copeland%27s_method
+pub struct Copeland(VotingSystemInfo<BattleBallots>);
+
+impl VotingSystem for Copeland {
+    type B = BattleBallots;
+
+    const NAME: &str = "copeland";
+    const LONG_NAME: &str = "Copeland's method";
+
+    fn with_parameters(choices: &[&str], parameters: VotingParameters) -> Self {
+        Self(VotingSystemInfo::new(
+            Self::LONG_NAME,
+            BallotForm::Ranked,
+            choices,
+            parameters,
+        ))
+    }
+
+    fn get_info(&self) -> &VotingSystemInfo<Self::B> {
+        &self.0
+    }
+
+    fn get_mut_info(&mut self) -> &mut VotingSystemInfo<Self::B> {
+        &mut self.0
+    }
+
+    fn result(&self) -> ElectionResult {
+        let b = self.0.get_ballot_box();
+
+        let scores: HashMap<String, f64> = b
+            .choices()
+            .map(|c1| {
+                let score: i64 = b
+                    .choices()
+                    .map(|c2| (b.duel(c1, c2) - b.duel(c2, c1)).signum())
+                    .sum();
+                (c1.to_owned(), score as f64)
+            })
+            .collect();
+        let mut ranking = rank_by_scores(&scores);
+
+        // Condorcet winner ?
+        if let Some(c) = b.condorcet_winner() {
+            move_first(&mut ranking, c);
+        }
+
+        let matrix = PairwiseMatrix::from(b);
+        let mut steps = vec![condorcet_step(&matrix)];
+        steps.extend(matrix.victories().into_iter().map(CountStep::Duel));
+        steps.push(CountStep::Scores {
+            label: "Victories minus defeats".to_string(),
+            scores: scores.clone(),
+        });
+
+        ElectionResult::new(Self::LONG_NAME, ranking, scores, self.0.get_count())
+            .with_explanation(steps)
+            .break_ties(self.0.get_parameters().get_tie_break(), &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copeland_method() {
+        let mut c = Copeland::new(&["A", "B", "C", "D"]);
+
+        // A > B > C > A and everyone beats D
+        for (n, v) in [(3, "ABCD"), (2, "BCAD"), (2, "CABD")] {
+            for _ in 0..n {
+                c.vote(SingleBallot::Ranked(
+                    v.chars().map(|c| c.to_string()).collect(),
+                ))
+                .unwrap();
+            }
+        }
+
+        let result = c.result();
+        assert!(result.is_tie());
+        assert_eq!(
+            vec!["A".to_string(), "B".to_string(), "C".to_string()],
+            result.get_winners()
+        );
+        assert_eq!(Some(1.), result.get_score("A"));
+        assert_eq!(Some(-3.), result.get_score("D"));
+    }
+}
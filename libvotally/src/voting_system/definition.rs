@@ -3,13 +3,18 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
+
+use crate::voting_system::{ElectionResult, TieBreak};
 
 /// Describe the ballot's form
-#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum BallotForm {
     Uninominal,
     Approved,
     Ranked,
+    Scored,
+    Graded,
 }
 
 impl fmt::Display for BallotForm {
@@ -21,6 +26,8 @@ impl fmt::Display for BallotForm {
                 BallotForm::Uninominal => "Uninominal",
                 BallotForm::Approved => "Approved",
                 BallotForm::Ranked => "Ranked",
+                BallotForm::Scored => "Scored",
+                BallotForm::Graded => "Graded",
             }
         )
     }
@@ -32,6 +39,11 @@ pub enum SingleBallot {
     Uninominal(String),
     Approved(Vec<String>),
     Ranked(Vec<String>),
+    /// Groups of equally ranked choices, from the most preferred to the least preferred
+    RankedWithTies(Vec<Vec<String>>),
+    Scored(HashMap<String, u8>),
+    /// Index of the grade given to each choice, 0 being the best grade
+    Graded(HashMap<String, u8>),
 }
 
 impl SingleBallot {
@@ -40,29 +52,152 @@ impl SingleBallot {
         match self {
             SingleBallot::Uninominal(_) => BallotForm::Uninominal,
             SingleBallot::Approved(_) => BallotForm::Approved,
-            SingleBallot::Ranked(_) => BallotForm::Ranked,
+            SingleBallot::Ranked(_) | SingleBallot::RankedWithTies(_) => BallotForm::Ranked,
+            SingleBallot::Scored(_) => BallotForm::Scored,
+            SingleBallot::Graded(_) => BallotForm::Graded,
+        }
+    }
+
+    /// Get every choice written on this ballot
+    pub fn choices(&self) -> Vec<&String> {
+        match self {
+            SingleBallot::Uninominal(c) => vec![c],
+            SingleBallot::Approved(choices) | SingleBallot::Ranked(choices) => {
+                choices.iter().collect()
+            }
+            SingleBallot::RankedWithTies(groups) => groups.iter().flatten().collect(),
+            SingleBallot::Scored(values) | SingleBallot::Graded(values) => values.keys().collect(),
+        }
+    }
+
+    /// Get the groups of equally ranked choices of a ranked ballot
+    fn ranking_groups(self) -> Option<Vec<Vec<String>>> {
+        match self {
+            SingleBallot::Ranked(ranked) => Some(ranked.into_iter().map(|c| vec![c]).collect()),
+            SingleBallot::RankedWithTies(groups) => Some(groups),
+            _ => None,
+        }
+    }
+
+    /// Create a ranked ballot from groups of equally ranked choices,
+    /// with ties only if a group holds several choices
+    fn from_ranking_groups(groups: Vec<Vec<String>>) -> SingleBallot {
+        if groups.iter().all(|group| group.len() == 1) {
+            SingleBallot::Ranked(groups.into_iter().flatten().collect())
+        } else {
+            SingleBallot::RankedWithTies(groups)
+        }
+    }
+
+    /// Derive a ballot of another form from this one, if the voter's preferences are kept.
+    /// Ranked ballots give their first choice when it isn't tied, scored and graded ballots
+    /// give their order of preference, choices with the same score or grade being ranked equally.
+    pub fn convert(&self, form: BallotForm) -> Option<SingleBallot> {
+        if self.ballot_form() == form {
+            return Some(self.clone());
         }
+
+        let groups = match self {
+            SingleBallot::Ranked(ranked) => ranked.iter().map(|c| vec![c.to_owned()]).collect(),
+            SingleBallot::RankedWithTies(groups) => groups.clone(),
+            SingleBallot::Scored(scores) => preference_order(scores, |s| u8::MAX - s),
+            SingleBallot::Graded(grades) => preference_order(grades, |g| g),
+            _ => return None,
+        };
+
+        match form {
+            BallotForm::Ranked => Some(SingleBallot::from_ranking_groups(groups)),
+            BallotForm::Uninominal => match &groups[..] {
+                [first, ..] if first.len() == 1 => Some(SingleBallot::Uninominal(first[0].clone())),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Ballot cast by a voter carrying several votes,
+/// such as a shareholder or a delegate holding several mandates
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::voting_system::{Plurality, SingleBallot, VotingSystem, WeightedBallot};
+///
+/// let mut p = Plurality::new(&["A", "B"]);
+///
+/// p.vote_weighted(WeightedBallot::new(SingleBallot::Uninominal("A".to_string()), 3))
+///     .unwrap();
+/// p.vote(SingleBallot::Uninominal("B".to_string())).unwrap();
+/// p.vote(SingleBallot::Uninominal("B".to_string())).unwrap();
+///
+/// assert_eq!(vec!["A".to_string()], p.result().get_winners());
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WeightedBallot {
+    /// The ballot itself
+    ballot: SingleBallot,
+    /// Number of votes carried by the ballot
+    weight: u32,
+}
+
+impl WeightedBallot {
+    /// Create a ballot carrying weight votes
+    pub fn new(ballot: SingleBallot, weight: u32) -> Self {
+        Self { ballot, weight }
+    }
+
+    /// Get the ballot
+    pub fn get_ballot(&self) -> &SingleBallot {
+        &self.ballot
+    }
+
+    /// Get the number of votes carried by the ballot
+    pub fn get_weight(&self) -> u32 {
+        self.weight
     }
 }
 
+impl From<SingleBallot> for WeightedBallot {
+    /// A ballot carrying a single vote
+    fn from(ballot: SingleBallot) -> Self {
+        Self::new(ballot, 1)
+    }
+}
+
+/// Sort choices by increasing key, grouping the choices with the same key
+fn preference_order(values: &HashMap<String, u8>, key: impl Fn(u8) -> u8) -> Vec<Vec<String>> {
+    let mut sorted: Vec<(&String, u8)> = values.iter().map(|(c, v)| (c, key(*v))).collect();
+    sorted.sort_by_key(|(c, k)| (*k, *c));
+
+    sorted
+        .chunk_by(|a, b| a.1 == b.1)
+        .map(|group| group.iter().map(|(c, _)| c.to_string()).collect())
+        .collect()
+}
+
 /// Trait for ballots boxes
 pub trait Ballots: Sized {
     /// Create a new ballots box
-    fn new(choices: &Vec<&str>) -> Self;
+    fn new(choices: &[&str]) -> Self;
 
     /// Get all available choices
     fn choices(&self) -> impl Iterator<Item = &String>;
 
+    /// Cast a vote carrying several votes in the ballots box
+    fn vote_weighted(&mut self, ballot: WeightedBallot) -> Result<(), InvalidBallot>;
+
     /// Cast a vote in the ballots box
-    fn vote(&mut self, ballot: SingleBallot) -> Result<(), InvalidBallot>;
+    fn vote(&mut self, ballot: SingleBallot) -> Result<(), InvalidBallot> {
+        self.vote_weighted(WeightedBallot::from(ballot))
+    }
 }
 
 /// Type for ballot boxes where each candidate has points
-pub struct PointBallots(pub(crate) HashMap<String, i32>);
+pub struct PointBallots(pub(crate) HashMap<String, i64>);
 
 impl Ballots for PointBallots {
-    fn new(choices: &Vec<&str>) -> Self {
-        let mut choices_hashmap: HashMap<String, i32> = HashMap::new();
+    fn new(choices: &[&str]) -> Self {
+        let mut choices_hashmap: HashMap<String, i64> = HashMap::new();
 
         choices.iter().for_each(|c| {
             choices_hashmap.insert(c.to_string(), 0);
@@ -75,31 +210,46 @@ impl Ballots for PointBallots {
         c.keys()
     }
 
-    fn vote(&mut self, ballot: SingleBallot) -> Result<(), InvalidBallot> {
+    fn vote_weighted(&mut self, ballot: WeightedBallot) -> Result<(), InvalidBallot> {
         let PointBallots(c) = self;
+        let WeightedBallot { ballot, weight } = ballot;
+        let weight = i64::from(weight);
 
         match ballot {
             SingleBallot::Uninominal(b) => {
                 c.get(&b)
                     .ok_or(InvalidBallot(format!("unknown candidate {}", b)))?;
-                c.entry(b).and_modify(|count| *count += 1);
+                c.entry(b).and_modify(|count| *count += weight);
             }
             SingleBallot::Approved(vec_approved) => {
                 for b in vec_approved {
                     c.get(&b)
                         .ok_or(InvalidBallot(format!("unknown candidate {}", b)))?;
-                    c.entry(b).and_modify(|count| *count += 1);
+                    c.entry(b).and_modify(|count| *count += weight);
                 }
             }
-            SingleBallot::Ranked(vec_ranked) => {
-                let mut rank = 1;
-                for b in vec_ranked {
+            SingleBallot::Ranked(_) | SingleBallot::RankedWithTies(_) => {
+                // Borda points: one point for each candidate ranked strictly below,
+                // unranked candidates being below every ranked one
+                let mut below = c.len() as i64;
+                for group in ballot.ranking_groups().unwrap() {
+                    below -= group.len() as i64;
+                    for b in group {
+                        c.get(&b)
+                            .ok_or(InvalidBallot(format!("unknown candidate {}", b)))?;
+                        c.entry(b).and_modify(|count| *count += below * weight);
+                    }
+                }
+            }
+            SingleBallot::Scored(scores) => {
+                for (b, score) in scores {
                     c.get(&b)
                         .ok_or(InvalidBallot(format!("unknown candidate {}", b)))?;
-                    c.entry(b).and_modify(|count| *count += rank);
-                    rank += 1;
+                    c.entry(b)
+                        .and_modify(|count| *count += score as i64 * weight);
                 }
-            } // _ => Err(InvalidBallot("Incompatible ballot form".to_string()))?
+            }
+            _ => Err(InvalidBallot("Incompatible ballot form".to_string()))?,
         }
 
         Ok(())
@@ -108,11 +258,28 @@ impl Ballots for PointBallots {
 
 /// Type for ballot boxes where each candidate is in a kind of battle
 /// with each other
-pub struct BattleBallots(pub(crate) HashMap<(String, String), i32>);
+pub struct BattleBallots(pub(crate) HashMap<(String, String), i64>);
+
+impl BattleBallots {
+    /// Get the number of ballots preferring c1 to c2
+    pub(crate) fn duel(&self, c1: &str, c2: &str) -> i64 {
+        let BattleBallots(b) = self;
+        b.get(&(c1.to_owned(), c2.to_owned())).copied().unwrap_or(0)
+    }
+
+    /// Find the candidate preferred to every other candidate by a majority, if any
+    pub(crate) fn condorcet_winner(&self) -> Option<&String> {
+        let mut ch = self.choices();
+        ch.find(|c1| {
+            self.choices()
+                .all(|c2| c1 == &c2 || self.duel(c1, c2) > self.duel(c2, c1))
+        })
+    }
+}
 
 impl Ballots for BattleBallots {
-    fn new(choices: &Vec<&str>) -> Self {
-        let mut choices_hashmap: HashMap<(String, String), i32> = HashMap::new();
+    fn new(choices: &[&str]) -> Self {
+        let mut choices_hashmap: HashMap<(String, String), i64> = HashMap::new();
 
         choices.iter().for_each(|c1| {
             choices.iter().for_each(|c2| {
@@ -127,25 +294,209 @@ impl Ballots for BattleBallots {
         HashSet::<&String>::from_iter(c.keys().map(|(a, _)| a)).into_iter()
     }
 
-    fn vote(&mut self, ballot: SingleBallot) -> Result<(), InvalidBallot> {
+    fn vote_weighted(&mut self, ballot: WeightedBallot) -> Result<(), InvalidBallot> {
         let BattleBallots(c) = self;
+        let WeightedBallot { ballot, weight } = ballot;
+        let weight = i64::from(weight);
 
         match ballot {
-            SingleBallot::Ranked(vec_ranked) => {
-                let choices = HashSet::<String>::from_iter(c.keys().cloned().map(|(a, _)| a));
+            SingleBallot::Ranked(_) | SingleBallot::RankedWithTies(_) => {
+                let mut below = HashSet::<String>::from_iter(c.keys().cloned().map(|(a, _)| a));
+
+                // A candidate is preferred to every candidate of the following groups
+                // and to unranked candidates, equally ranked candidates have no preference
+                for group in ballot.ranking_groups().unwrap() {
+                    for b in &group {
+                        // Check if b is an available choice
+                        if !below.remove(b) {
+                            Err(InvalidBallot(format!("unknown candidate {}", b)))?
+                        }
+                    }
 
-                let mut iter_ranked = vec_ranked.into_iter();
+                    for b1 in &group {
+                        below.iter().for_each(|b2| {
+                            c.entry((b1.clone(), b2.clone()))
+                                .and_modify(|count| *count += weight);
+                        })
+                    }
+                }
+            }
+            SingleBallot::Scored(scores) => {
+                if let Some(b) = scores
+                    .keys()
+                    .find(|b| !c.contains_key(&(b.to_string(), b.to_string())))
+                {
+                    Err(InvalidBallot(format!("unknown candidate {}", b)))?
+                }
 
-                // for b1 in iter_ranked {
-                while let Some(b1) = iter_ranked.next() {
-                    // Check if b is an available choice
-                    if !choices.contains(&b1) {
-                        Err(InvalidBallot(format!("unknown candidate {}", b1)))?
+                // A candidate is preferred to another if it has a higher score
+                for (b1, s1) in scores.iter() {
+                    for (b2, s2) in scores.iter() {
+                        if s1 > s2 {
+                            c.entry((b1.clone(), b2.clone()))
+                                .and_modify(|count| *count += weight);
+                        }
                     }
+                }
+            }
+            _ => Err(InvalidBallot("Incompatible ballot form".to_string()))?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Type for ballot boxes keeping every ranked ballot
+pub struct RankedBallots {
+    /// Differents choices
+    pub(crate) choices: Vec<String>,
+    /// Each ballot as groups of equally ranked choices,
+    /// from the most preferred to the least preferred
+    pub(crate) ballots: Vec<Vec<Vec<String>>>,
+    /// Number of votes carried by each ballot
+    pub(crate) weights: Vec<u64>,
+}
 
-                    iter_ranked.clone().for_each(|b2| {
-                        c.entry((b1.clone(), b2)).and_modify(|count| *count += 1);
-                    })
+impl RankedBallots {
+    /// Get the most preferred choices of a ballot among the remaining ones,
+    /// several if they are ranked equally
+    pub(crate) fn preferred<'a>(
+        ballot: &'a [Vec<String>],
+        remaining: &[String],
+    ) -> Vec<&'a String> {
+        ballot
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .filter(|c| remaining.contains(c))
+                    .collect::<Vec<_>>()
+            })
+            .find(|group| !group.is_empty())
+            .unwrap_or_default()
+    }
+}
+
+impl Ballots for RankedBallots {
+    fn new(choices: &[&str]) -> Self {
+        Self {
+            choices: choices.iter().map(|c| c.to_string()).collect(),
+            ballots: Vec::new(),
+            weights: Vec::new(),
+        }
+    }
+
+    fn choices(&self) -> impl Iterator<Item = &String> {
+        self.choices.iter()
+    }
+
+    fn vote_weighted(&mut self, ballot: WeightedBallot) -> Result<(), InvalidBallot> {
+        let WeightedBallot { ballot, weight } = ballot;
+
+        match ballot {
+            SingleBallot::Ranked(_) | SingleBallot::RankedWithTies(_) => {
+                let groups = ballot.ranking_groups().unwrap();
+                if let Some(b) = groups.iter().flatten().find(|b| !self.choices.contains(b)) {
+                    Err(InvalidBallot(format!("unknown candidate {}", b)))?
+                }
+
+                self.ballots.push(groups);
+                self.weights.push(u64::from(weight));
+            }
+            _ => Err(InvalidBallot("Incompatible ballot form".to_string()))?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Type for ballot boxes keeping every accepted ballot in order
+/// so that they can be counted again with any voting system
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RawBallots {
+    /// Differents choices
+    choices: Vec<String>,
+    /// Each ballot in the order they were cast
+    ballots: Vec<WeightedBallot>,
+}
+
+impl RawBallots {
+    /// Create a ballot box from already cast ballots
+    pub fn from_ballots(choices: &[&str], ballots: Vec<SingleBallot>) -> Self {
+        Self::from_weighted_ballots(
+            choices,
+            ballots.into_iter().map(WeightedBallot::from).collect(),
+        )
+    }
+
+    /// Create a ballot box from already cast ballots carrying several votes
+    pub fn from_weighted_ballots(choices: &[&str], ballots: Vec<WeightedBallot>) -> Self {
+        Self {
+            choices: choices.iter().map(|c| c.to_string()).collect(),
+            ballots,
+        }
+    }
+
+    /// Get every ballot in the order they were cast
+    pub fn get_ballots(&self) -> &[WeightedBallot] {
+        &self.ballots
+    }
+
+    /// Get all choices
+    pub fn get_choices(&self) -> &[String] {
+        &self.choices
+    }
+}
+
+impl Ballots for RawBallots {
+    fn new(choices: &[&str]) -> Self {
+        Self::from_ballots(choices, Vec::new())
+    }
+
+    fn choices(&self) -> impl Iterator<Item = &String> {
+        self.choices.iter()
+    }
+
+    fn vote_weighted(&mut self, ballot: WeightedBallot) -> Result<(), InvalidBallot> {
+        self.ballots.push(ballot);
+        Ok(())
+    }
+}
+
+/// Type for ballot boxes counting how many times each grade is given to each candidate
+pub struct GradeBallots(pub(crate) HashMap<String, Vec<u64>>);
+
+impl Ballots for GradeBallots {
+    fn new(choices: &[&str]) -> Self {
+        Self(
+            choices
+                .iter()
+                .map(|c| (c.to_string(), Vec::new()))
+                .collect(),
+        )
+    }
+
+    fn choices(&self) -> impl Iterator<Item = &String> {
+        let GradeBallots(c) = self;
+        c.keys()
+    }
+
+    fn vote_weighted(&mut self, ballot: WeightedBallot) -> Result<(), InvalidBallot> {
+        let GradeBallots(c) = self;
+        let WeightedBallot { ballot, weight } = ballot;
+
+        match ballot {
+            SingleBallot::Graded(grades) => {
+                if let Some(b) = grades.keys().find(|b| !c.contains_key(*b)) {
+                    Err(InvalidBallot(format!("unknown candidate {}", b)))?
+                }
+
+                for (b, grade) in grades {
+                    let distribution = c.get_mut(&b).unwrap();
+                    if distribution.len() <= grade as usize {
+                        distribution.resize(grade as usize + 1, 0);
+                    }
+                    distribution[grade as usize] += u64::from(weight);
                 }
             }
             _ => Err(InvalidBallot("Incompatible ballot form".to_string()))?,
@@ -156,7 +507,7 @@ impl Ballots for BattleBallots {
 }
 
 /// Describe minimal information need to an election
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MinimalVotingSystemInfo {
     /// The name of the voting system
     name: String,
@@ -164,6 +515,10 @@ pub struct MinimalVotingSystemInfo {
     choices: Vec<String>,
     /// The ballots' form
     ballot_form: BallotForm,
+    /// Parameters of the election
+    parameters: VotingParameters,
+    /// The voting system fills several seats
+    multi_winner: bool,
 }
 
 impl MinimalVotingSystemInfo {
@@ -173,9 +528,23 @@ impl MinimalVotingSystemInfo {
             name: name.to_owned(),
             choices,
             ballot_form,
+            parameters: VotingParameters::default(),
+            multi_winner: false,
         }
     }
 
+    /// Set the parameters of the election
+    pub fn with_parameters(mut self, parameters: VotingParameters) -> MinimalVotingSystemInfo {
+        self.parameters = parameters;
+        self
+    }
+
+    /// Set whether the voting system fills several seats
+    pub fn with_multi_winner(mut self, multi_winner: bool) -> MinimalVotingSystemInfo {
+        self.multi_winner = multi_winner;
+        self
+    }
+
     /// Shuffle choices
     pub fn shuffle_choices(mut self) -> MinimalVotingSystemInfo {
         self.choices.shuffle(&mut rng());
@@ -192,37 +561,58 @@ impl MinimalVotingSystemInfo {
         self.ballot_form
     }
 
+    /// Get the parameters of the election
+    pub fn get_parameters(&self) -> &VotingParameters {
+        &self.parameters
+    }
+
+    /// Check if the voting system fills several seats
+    pub fn is_multi_winner(&self) -> bool {
+        self.multi_winner
+    }
+
     /// Check if ballot could be cast or not
     pub fn check_ballot(&self, ballot: &SingleBallot) -> Result<(), InvalidBallot> {
         match (self.ballot_form, ballot) {
             (BallotForm::Uninominal, SingleBallot::Uninominal(b)) => {
-                if self.choices.contains(&b) {
+                if self.choices.contains(b) {
                     Ok(())
                 } else {
-                    Err(InvalidBallot(format!(
-                        "Ballot didn't contain a available choice"
-                    )))
+                    Err(InvalidBallot(
+                        "Ballot didn't contain a available choice".to_string(),
+                    ))
                 }
             }
             (BallotForm::Approved, SingleBallot::Approved(vec_b)) => {
                 let mut uniques = HashSet::new();
                 if vec_b
                     .iter()
-                    .all(|b| self.choices.contains(&b) && uniques.insert(b.clone()))
+                    .all(|b| self.choices.contains(b) && uniques.insert(b.clone()))
                 {
                     Ok(())
                 } else {
-                    Err(InvalidBallot(format!(
-                        "Ballot contains an unavailable choice"
-                    )))
+                    Err(InvalidBallot(
+                        "Ballot contains an unavailable choice".to_string(),
+                    ))
                 }
             }
-            (BallotForm::Ranked, SingleBallot::Ranked(vec_b)) => {
+            (BallotForm::Ranked, SingleBallot::RankedWithTies(_))
+                if !self.parameters.get_partial_ranking() =>
+            {
+                Err(InvalidBallot(
+                    "Equal rankings are not allowed in this election".to_string(),
+                ))
+            }
+            (BallotForm::Ranked, SingleBallot::Ranked(_) | SingleBallot::RankedWithTies(_)) => {
+                let groups = ballot.clone().ranking_groups().unwrap();
                 let mut uniques = HashSet::new();
-                if vec_b
-                    .iter()
-                    .all(|b| self.choices.contains(&b) && uniques.insert(b.clone()))
-                    && uniques.len() == self.choices.len()
+                if groups.iter().all(|group| !group.is_empty())
+                    && groups
+                        .iter()
+                        .flatten()
+                        .all(|b| self.choices.contains(b) && uniques.insert(b.clone()))
+                    && (self.parameters.get_partial_ranking()
+                        || uniques.len() == self.choices.len())
                 {
                     Ok(())
                 } else {
@@ -232,6 +622,42 @@ impl MinimalVotingSystemInfo {
                     )))
                 }
             }
+            (BallotForm::Graded, SingleBallot::Graded(grades)) => {
+                let scale = self.parameters.get_grades();
+                if let Some((b, g)) = grades.iter().find(|(_, g)| **g as usize >= scale.len()) {
+                    Err(InvalidBallot(format!("Unknown grade {} for {}", g, b)))
+                } else if grades.keys().all(|b| self.choices.contains(b))
+                    && grades.len() == self.choices.len()
+                {
+                    Ok(())
+                } else {
+                    Err(InvalidBallot(format!(
+                        "Ballot contains an unavailable choice or doesn't grade every choice: {:?}",
+                        ballot
+                    )))
+                }
+            }
+            (BallotForm::Scored, SingleBallot::Scored(scores)) => {
+                let range = self.parameters.get_score_range();
+                if let Some((b, s)) = scores.iter().find(|(_, s)| !range.contains(**s)) {
+                    Err(InvalidBallot(format!(
+                        "Score {} of {} is not between {} and {}",
+                        s,
+                        b,
+                        range.get_min(),
+                        range.get_max()
+                    )))
+                } else if scores.keys().all(|b| self.choices.contains(b))
+                    && scores.len() == self.choices.len()
+                {
+                    Ok(())
+                } else {
+                    Err(InvalidBallot(format!(
+                        "Ballot contains an unavailable choice or doesn't score every choice: {:?}",
+                        ballot
+                    )))
+                }
+            }
             _ => Err(InvalidBallot(format!(
                 "invalid ballot form : {} instead of {}",
                 ballot.ballot_form(),
@@ -253,30 +679,279 @@ impl fmt::Display for MinimalVotingSystemInfo {
         }
         writeln!(f)?;
 
-        write!(f, "Type of ballots: {}", self.ballot_form)
+        write!(f, "Type of ballots: {}", self.ballot_form)?;
+        if self.ballot_form == BallotForm::Scored {
+            write!(f, " from {}", self.parameters.get_score_range())?;
+        }
+        if self.ballot_form == BallotForm::Ranked && self.parameters.get_partial_ranking() {
+            write!(f, "\nChoices can be left out or ranked equally")?;
+        }
+        if self.ballot_form == BallotForm::Graded {
+            write!(
+                f,
+                "\nGrades from best to worst: {}",
+                self.parameters.get_grades().join(", ")
+            )?;
+        }
+        if self.multi_winner {
+            write!(f, "\nNumber of seats: {}", self.parameters.get_seats())?;
+        }
+
+        Ok(())
     }
 }
 
+/// Range of the scores a voter can give to each choice
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreRange {
+    /// The lowest score
+    min: u8,
+    /// The highest score
+    max: u8,
+}
+
+impl ScoreRange {
+    /// Create a new score range
+    pub fn new(min: u8, max: u8) -> Result<Self, InvalidScoreRange> {
+        if min < max {
+            Ok(Self { min, max })
+        } else {
+            Err(InvalidScoreRange(format!("{}-{}", min, max)))
+        }
+    }
+
+    /// Get the lowest score
+    pub fn get_min(&self) -> u8 {
+        self.min
+    }
+
+    /// Get the highest score
+    pub fn get_max(&self) -> u8 {
+        self.max
+    }
+
+    /// Return true if score is in the range
+    pub fn contains(&self, score: u8) -> bool {
+        self.min <= score && score <= self.max
+    }
+}
+
+impl Default for ScoreRange {
+    fn default() -> Self {
+        Self { min: 0, max: 5 }
+    }
+}
+
+impl fmt::Display for ScoreRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.min, self.max)
+    }
+}
+
+impl FromStr for ScoreRange {
+    type Err = InvalidScoreRange;
+
+    /// Parse a range written `<min>-<max>`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (min, max) = s.split_once('-').ok_or(InvalidScoreRange(s.to_string()))?;
+        let min = min
+            .trim()
+            .parse()
+            .map_err(|_| InvalidScoreRange(s.to_string()))?;
+        let max = max
+            .trim()
+            .parse()
+            .map_err(|_| InvalidScoreRange(s.to_string()))?;
+
+        ScoreRange::new(min, max)
+    }
+}
+
+/// Error for invalid score range
+#[derive(Debug)]
+pub struct InvalidScoreRange(String);
+
+impl fmt::Display for InvalidScoreRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid score range: {}", self.0)
+    }
+}
+
+impl Error for InvalidScoreRange {}
+
+/// Parameters of an election which don't depend on the voting system
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VotingParameters {
+    /// Policy used to break ties
+    tie_break: TieBreak,
+    /// Number of seats to fill
+    seats: usize,
+    /// Range of scores for scored ballots
+    score_range: ScoreRange,
+    /// Grades for graded ballots, from the best to the worst
+    grades: Vec<String>,
+    /// Allow ranked ballots leaving out choices or ranking several choices equally
+    partial_ranking: bool,
+}
+
+impl Default for VotingParameters {
+    fn default() -> Self {
+        Self {
+            tie_break: TieBreak::default(),
+            seats: 1,
+            score_range: ScoreRange::default(),
+            grades: ["Excellent", "Very good", "Good", "Fair", "Poor", "Reject"]
+                .iter()
+                .map(|g| g.to_string())
+                .collect(),
+            partial_ranking: false,
+        }
+    }
+}
+
+impl VotingParameters {
+    /// Set the tie-breaking policy
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> VotingParameters {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Get the tie-breaking policy
+    pub fn get_tie_break(&self) -> &TieBreak {
+        &self.tie_break
+    }
+
+    /// Set the number of seats to fill
+    pub fn with_seats(mut self, seats: usize) -> VotingParameters {
+        self.seats = seats;
+        self
+    }
+
+    /// Get the number of seats to fill
+    pub fn get_seats(&self) -> usize {
+        self.seats
+    }
+
+    /// Set the range of scores for scored ballots
+    pub fn with_score_range(mut self, score_range: ScoreRange) -> VotingParameters {
+        self.score_range = score_range;
+        self
+    }
+
+    /// Get the range of scores for scored ballots
+    pub fn get_score_range(&self) -> ScoreRange {
+        self.score_range
+    }
+
+    /// Set the grades for graded ballots, from the best to the worst
+    pub fn with_grades(mut self, grades: Vec<String>) -> VotingParameters {
+        self.grades = grades;
+        self
+    }
+
+    /// Get the grades for graded ballots, from the best to the worst
+    pub fn get_grades(&self) -> &Vec<String> {
+        &self.grades
+    }
+
+    /// Allow ranked ballots leaving out choices or ranking several choices equally
+    pub fn with_partial_ranking(mut self, partial_ranking: bool) -> VotingParameters {
+        self.partial_ranking = partial_ranking;
+        self
+    }
+
+    /// Get whether ranked ballots can leave out choices or rank several choices equally
+    pub fn get_partial_ranking(&self) -> bool {
+        self.partial_ranking
+    }
+
+    /// Check that an election between choices can be held with these parameters
+    pub fn validate(&self, choices: &[&str]) -> Result<(), InvalidParameters> {
+        let invalid = |message: String| Err(InvalidParameters(message));
+
+        if choices.len() <= 1 {
+            return invalid("there is not enough choices".to_string());
+        }
+        let mut names = HashSet::new();
+        if let Some(c) = choices
+            .iter()
+            .find(|c| c.trim().is_empty() || !names.insert(*c))
+        {
+            return invalid(format!("the choice \"{}\" is empty or repeated", c));
+        }
+
+        if self.seats == 0 || self.seats >= choices.len() {
+            return invalid(
+                "the number of seats must be between 1 and the number of choices minus 1"
+                    .to_string(),
+            );
+        }
+        if self.score_range.get_min() >= self.score_range.get_max() {
+            return invalid(format!("invalid score range {}", self.score_range));
+        }
+        if self.grades.is_empty() || self.grades.len() > u8::MAX as usize {
+            return invalid(format!(
+                "the number of grades must be between 1 and {}",
+                u8::MAX
+            ));
+        }
+        if let TieBreak::Priority(priority) = &self.tie_break
+            && let Some(c) = priority.iter().find(|c| !choices.contains(&c.as_str()))
+        {
+            return invalid(format!("unknown choice {} in the tie-break priority", c));
+        }
+
+        Ok(())
+    }
+}
+
+/// Error for parameters which don't suit an election
+#[derive(Debug)]
+pub struct InvalidParameters(pub(crate) String);
+
+impl fmt::Display for InvalidParameters {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid parameters: {}", self.0)
+    }
+}
+
+impl Error for InvalidParameters {}
+
 /// Contain all the information needed to an election
 pub struct VotingSystemInfo<B: Ballots> {
     /// The name of the voting system
     name: String,
     /// The ballots' form
     ballot_form: BallotForm,
+    /// Parameters of the election
+    parameters: VotingParameters,
     /// All ballots of the voting system
     ballot_box: B,
+    /// Every accepted ballot, kept as cast
+    raw_ballots: RawBallots,
     /// Total number of ballots
     count: usize,
+    /// Total number of votes carried by the ballots
+    total_weight: u64,
 }
 
 impl<B: Ballots> VotingSystemInfo<B> {
     /// Create a new voting system info
-    pub(crate) fn new(name: &str, ballot_form: BallotForm, choices: &Vec<&str>) -> Self {
+    pub(crate) fn new(
+        name: &str,
+        ballot_form: BallotForm,
+        choices: &[&str],
+        parameters: VotingParameters,
+    ) -> Self {
         Self {
             name: name.to_owned(),
             ballot_form,
+            parameters,
             ballot_box: B::new(choices),
+            raw_ballots: RawBallots::new(choices),
             count: 0,
+            total_weight: 0,
         }
     }
 
@@ -295,16 +970,31 @@ impl<B: Ballots> VotingSystemInfo<B> {
         self.ballot_form
     }
 
+    /// Get the parameters of the election
+    pub fn get_parameters(&self) -> &VotingParameters {
+        &self.parameters
+    }
+
     /// Get the ballot box
     pub fn get_ballot_box(&self) -> &B {
         &self.ballot_box
     }
 
+    /// Get every accepted ballot in the order they were cast
+    pub fn get_raw_ballots(&self) -> &RawBallots {
+        &self.raw_ballots
+    }
+
     /// Get the total number of ballots
     pub fn get_count(&self) -> usize {
         self.count
     }
 
+    /// Get the total number of votes carried by the ballots
+    pub fn get_total_weight(&self) -> u64 {
+        self.total_weight
+    }
+
     /// Get the MinimalVotingSystemInfo representing this VotingSystemInfo
     pub fn get_minimal_info(&self) -> MinimalVotingSystemInfo {
         MinimalVotingSystemInfo::new(
@@ -312,15 +1002,29 @@ impl<B: Ballots> VotingSystemInfo<B> {
             self.ballot_form,
             self.get_choices().map(|s| s.to_owned()).collect(),
         )
+        .with_parameters(self.parameters.clone())
     }
 
     /// Just vote
     pub fn vote(&mut self, ballot: SingleBallot) -> Result<(), InvalidBallot> {
-        self.get_minimal_info().check_ballot(&ballot)?;
+        self.vote_weighted(WeightedBallot::from(ballot))
+    }
+
+    /// Vote with a ballot carrying several votes
+    pub fn vote_weighted(&mut self, ballot: WeightedBallot) -> Result<(), InvalidBallot> {
+        if ballot.weight == 0 {
+            Err(InvalidBallot(
+                "a ballot must carry at least one vote".to_string(),
+            ))?
+        }
+        self.get_minimal_info().check_ballot(&ballot.ballot)?;
 
-        self.ballot_box.vote(ballot)?;
+        let weight = ballot.weight;
+        self.ballot_box.vote_weighted(ballot.clone())?;
+        self.raw_ballots.vote_weighted(ballot)?;
 
         self.count += 1;
+        self.total_weight += u64::from(weight);
         Ok(())
     }
 }
@@ -330,13 +1034,23 @@ pub trait VotingSystem {
 
     const NAME: &str;
     const LONG_NAME: &str;
+    /// The voting system fills several seats
+    const MULTI_WINNER: bool = false;
+
+    /// Create a new election with default parameters
+    fn new(choices: &[&str]) -> Self
+    where
+        Self: Sized,
+    {
+        Self::with_parameters(choices, VotingParameters::default())
+    }
 
     /// Create a new election
-    fn new(choices: &Vec<&str>) -> Self;
+    fn with_parameters(choices: &[&str], parameters: VotingParameters) -> Self;
 
     /// Algorithm finding the result of the election from all ballots
     // fn result_algorithm(ballots: &Ballots) -> String;
-    fn result(&self) -> String;
+    fn result(&self) -> ElectionResult;
 
     /// Get all information about this election
     fn get_info(&self) -> &VotingSystemInfo<Self::B>;
@@ -349,13 +1063,25 @@ pub trait VotingSystem {
         self.get_mut_info().vote(ballot)
     }
 
+    /// Cast ballot carrying several votes
+    fn vote_weighted(&mut self, ballot: WeightedBallot) -> Result<(), InvalidBallot> {
+        self.get_mut_info().vote_weighted(ballot)
+    }
+
     /// Get minimal information about this election
     fn get_minimal_info(&self) -> MinimalVotingSystemInfo {
-        self.get_info().get_minimal_info()
+        self.get_info()
+            .get_minimal_info()
+            .with_multi_winner(Self::MULTI_WINNER)
+    }
+
+    /// Get every accepted ballot, to count them again with another voting system
+    fn get_raw_ballots(&self) -> &RawBallots {
+        self.get_info().get_raw_ballots()
     }
 
     // Calculate the election's result
-    // fn result(&mut self) -> String {
+    // fn result(&mut self) -> ElectionResult {
     //     Self::result_algorithm(&self.get_info().ballot_box)
     // }
 }
@@ -375,10 +1101,11 @@ impl Error for InvalidBallot {}
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::voting_system::{BordaCount, InstantRunoff, Plurality, recount};
 
     #[test]
     fn ballot_trait() {
-        let mut p = PointBallots::new(&vec!["A", "B", "C"]);
+        let mut p = PointBallots::new(&["A", "B", "C"]);
         assert_eq!(
             HashSet::<&String>::from_iter(p.choices()),
             HashSet::from_iter(
@@ -387,13 +1114,26 @@ mod test {
         );
         assert!(p.vote(SingleBallot::Uninominal("D".to_string())).is_err());
 
-        let b = BattleBallots::new(&vec!["A", "B", "C"]);
+        let b = BattleBallots::new(&["A", "B", "C"]);
         assert_eq!(
             HashSet::<&String>::from_iter(b.choices()),
             HashSet::from_iter(
                 vec![&"A".to_string(), &"B".to_string(), &"C".to_string()].into_iter()
             )
         );
+
+        let mut r = RankedBallots::new(&["A", "B", "C"]);
+        assert_eq!(
+            HashSet::<&String>::from_iter(r.choices()),
+            HashSet::from_iter(
+                vec![&"A".to_string(), &"B".to_string(), &"C".to_string()].into_iter()
+            )
+        );
+        assert!(
+            r.vote(SingleBallot::Ranked(vec!["A".to_string(), "D".to_string()]))
+                .is_err()
+        );
+        assert!(r.vote(SingleBallot::Uninominal("A".to_string())).is_err());
     }
 
     #[test]
@@ -462,5 +1202,213 @@ mod test {
             mvsi.check_ballot(&SingleBallot::Uninominal("A".to_string()))
                 .is_err()
         );
+        assert!(
+            mvsi.check_ballot(&SingleBallot::RankedWithTies(vec![
+                vec!["A".to_string()],
+                vec!["B".to_string(), "C".to_string()]
+            ]))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn ballot_partial_ranked() {
+        let mvsi = MinimalVotingSystemInfo::new(
+            "test",
+            BallotForm::Ranked,
+            vec![String::from("A"), String::from("B"), String::from("C")],
+        )
+        .with_parameters(VotingParameters::default().with_partial_ranking(true));
+
+        let truncated = SingleBallot::Ranked(vec!["A".to_string()]);
+        let tied = SingleBallot::RankedWithTies(vec![
+            vec!["B".to_string(), "C".to_string()],
+            vec!["A".to_string()],
+        ]);
+        assert!(mvsi.check_ballot(&truncated).is_ok());
+        assert!(mvsi.check_ballot(&tied).is_ok());
+        assert!(
+            mvsi.check_ballot(&SingleBallot::RankedWithTies(vec![
+                vec!["A".to_string()],
+                vec!["A".to_string()]
+            ]))
+            .is_err()
+        );
+
+        let mut p = PointBallots::new(&["A", "B", "C"]);
+        let mut b = BattleBallots::new(&["A", "B", "C"]);
+        for ballot in [truncated, tied] {
+            p.vote(ballot.clone()).unwrap();
+            b.vote(ballot).unwrap();
+        }
+
+        // A is above both unranked candidates, then below both tied ones
+        assert_eq!((2, 1, 1), (p.0["A"], p.0["B"], p.0["C"]));
+        assert_eq!(
+            (1, 1, 0, 0),
+            (
+                b.duel("A", "B"),
+                b.duel("B", "A"),
+                b.duel("B", "C"),
+                b.duel("C", "B")
+            )
+        );
+    }
+
+    #[test]
+    fn ballot_scored() {
+        let mvsi = MinimalVotingSystemInfo::new(
+            "test",
+            BallotForm::Scored,
+            vec![String::from("A"), String::from("B"), String::from("C")],
+        )
+        .with_parameters(
+            VotingParameters::default().with_score_range(ScoreRange::new(1, 10).unwrap()),
+        );
+
+        let scores = |a, b, c| {
+            SingleBallot::Scored(HashMap::from([
+                ("A".to_string(), a),
+                ("B".to_string(), b),
+                ("C".to_string(), c),
+            ]))
+        };
+
+        assert!(mvsi.check_ballot(&scores(1, 10, 5)).is_ok());
+        assert!(mvsi.check_ballot(&scores(0, 10, 5)).is_err());
+        assert!(mvsi.check_ballot(&scores(1, 11, 5)).is_err());
+        assert!(
+            mvsi.check_ballot(&SingleBallot::Scored(HashMap::from([("A".to_string(), 3)])))
+                .is_err()
+        );
+        assert!(
+            mvsi.check_ballot(&SingleBallot::Uninominal("A".to_string()))
+                .is_err()
+        );
+
+        assert_eq!(
+            Ok(ScoreRange::new(0, 10).unwrap()),
+            "0-10".parse().map_err(|_| ())
+        );
+        assert!("5-2".parse::<ScoreRange>().is_err());
+    }
+
+    #[test]
+    fn ballot_graded() {
+        let mvsi = MinimalVotingSystemInfo::new(
+            "test",
+            BallotForm::Graded,
+            vec![String::from("A"), String::from("B")],
+        )
+        .with_parameters(
+            VotingParameters::default().with_grades(vec!["Good".to_string(), "Bad".to_string()]),
+        );
+
+        let grades = |a, b| {
+            SingleBallot::Graded(HashMap::from([("A".to_string(), a), ("B".to_string(), b)]))
+        };
+
+        assert!(mvsi.check_ballot(&grades(0, 1)).is_ok());
+        assert!(mvsi.check_ballot(&grades(0, 2)).is_err());
+        assert!(
+            mvsi.check_ballot(&SingleBallot::Graded(HashMap::from([("A".to_string(), 0)])))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn raw_ballots() {
+        let mut borda = BordaCount::new(&["A", "B", "C"]);
+
+        for v in ["ABC", "ABC", "BCA", "BCA", "CBA"] {
+            borda
+                .vote(SingleBallot::Ranked(
+                    v.chars().map(|c| c.to_string()).collect(),
+                ))
+                .unwrap();
+        }
+        assert!(
+            borda
+                .vote(SingleBallot::Uninominal("A".to_string()))
+                .is_err()
+        );
+
+        let raw = borda.get_raw_ballots();
+        assert_eq!(5, raw.get_ballots().len());
+        assert_eq!(vec!["B".to_string()], borda.result().get_winners());
+
+        // C is eliminated first and its ballot goes to B
+        let irv = recount("irv", raw, &VotingParameters::default()).unwrap();
+        assert_eq!(vec!["B".to_string()], irv.get_winners());
+
+        assert!(recount("plurality", raw, &VotingParameters::default()).is_err());
+    }
+
+    #[test]
+    fn weighted_ballots() {
+        let ranked =
+            |v: &str| SingleBallot::Ranked(v.chars().map(|c| c.to_string()).collect::<Vec<_>>());
+
+        let mut p = PointBallots::new(&["A", "B", "C"]);
+        p.vote_weighted(WeightedBallot::new(
+            SingleBallot::Uninominal("A".to_string()),
+            3,
+        ))
+        .unwrap();
+        p.vote_weighted(WeightedBallot::new(ranked("ABC"), 2))
+            .unwrap();
+        assert_eq!(Some(&7), p.0.get("A"));
+        assert_eq!(Some(&2), p.0.get("B"));
+
+        // Weights of large shareholders neither wrap around nor overflow the totals
+        let mut large = Plurality::new(&["A", "B"]);
+        for _ in 0..3 {
+            large
+                .vote_weighted(WeightedBallot::new(
+                    SingleBallot::Uninominal("A".to_string()),
+                    u32::MAX,
+                ))
+                .unwrap();
+        }
+        large
+            .vote(SingleBallot::Uninominal("B".to_string()))
+            .unwrap();
+        assert_eq!(
+            3 * u64::from(u32::MAX) + 1,
+            large.get_info().get_total_weight()
+        );
+        let result = large.result();
+        assert_eq!(vec!["A".to_string()], result.get_winners());
+        assert_eq!(Some(3.0 * u32::MAX as f64), result.get_score("A"));
+
+        let mut b = BattleBallots::new(&["A", "B"]);
+        b.vote_weighted(WeightedBallot::new(ranked("AB"), u32::MAX))
+            .unwrap();
+        assert_eq!(i64::from(u32::MAX), b.duel("A", "B"));
+
+        let mut b = BattleBallots::new(&["A", "B", "C"]);
+        b.vote_weighted(WeightedBallot::new(ranked("BAC"), 5))
+            .unwrap();
+        b.vote(ranked("ABC")).unwrap();
+        assert_eq!(5, b.duel("B", "A"));
+        assert_eq!(1, b.duel("A", "B"));
+
+        let mut irv = InstantRunoff::new(&["A", "B", "C"]);
+        for (v, w) in [("ABC", 2), ("BCA", 1), ("CBA", 2)] {
+            irv.vote_weighted(WeightedBallot::new(ranked(v), w))
+                .unwrap();
+        }
+        assert!(
+            irv.vote_weighted(WeightedBallot::new(ranked("ABC"), 0))
+                .is_err()
+        );
+        assert_eq!(3, irv.get_info().get_count());
+        assert_eq!(5, irv.get_info().get_total_weight());
+
+        // B is eliminated first and its ballot goes to C
+        assert_eq!(vec!["C".to_string()], irv.result().get_winners());
+        let recounted =
+            recount("irv", irv.get_raw_ballots(), &VotingParameters::default()).unwrap();
+        assert_eq!(vec!["C".to_string()], recounted.get_winners());
     }
 }
@@ -0,0 +1,294 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::voting_system::result::{format_score, rank_by_scores};
+use crate::voting_system::{PairwiseMatrix, PairwiseVictory};
+
+/// One step of the count of an election, to explain how the result was found
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CountStep {
+    /// Scores of the candidates at some stage of the count
+    Scores {
+        label: String,
+        scores: HashMap<String, f64>,
+    },
+    /// A candidate beats every other one in one-to-one comparisons
+    CondorcetWinner(String),
+    /// No candidate beats every other one,
+    /// with a cycle of pairwise victories if there is one
+    NoCondorcetWinner {
+        smith_set: Vec<String>,
+        cycle: Option<Vec<String>>,
+    },
+    /// One-to-one comparison between two candidates
+    Duel(PairwiseVictory),
+    /// Pairwise victory kept in the ranking
+    Locked(PairwiseVictory),
+    /// Pairwise victory left out because it would create a cycle with locked victories
+    Skipped(PairwiseVictory),
+    /// The strongest path from the winner to the loser is stronger than the opposite one,
+    /// the strength of a path being its weakest pairwise victory
+    StrongestPath {
+        winner: String,
+        loser: String,
+        strength: i64,
+        opposite: i64,
+    },
+    /// Median grade of each candidate, in ranking order,
+    /// once the given number of median grades were removed from their grades
+    MedianGrades {
+        removed: usize,
+        grades: Vec<(String, String)>,
+    },
+    /// Candidates reaching the quota are elected
+    Elected(Vec<String>),
+    /// Surplus of an elected candidate transferred to the next preferences,
+    /// each ballot keeping the given share of its value
+    SurplusTransferred {
+        candidate: String,
+        surplus: f64,
+        ratio: f64,
+    },
+    /// Candidates eliminated, their ballots going to the next preferences
+    Eliminated(Vec<String>),
+    /// Tied candidates ordered by the tie-breaking policy
+    TieBroken {
+        tied: Vec<String>,
+        ranking: Vec<Vec<String>>,
+    },
+}
+
+/// Step recording whether a candidate beats every other one
+pub(crate) fn condorcet_step(matrix: &PairwiseMatrix) -> CountStep {
+    match matrix.condorcet_winner() {
+        Some(c) => CountStep::CondorcetWinner(c.to_owned()),
+        None => CountStep::NoCondorcetWinner {
+            smith_set: matrix.smith_set(),
+            cycle: matrix.find_cycle(),
+        },
+    }
+}
+
+/// Step recording the worst candidates chosen among tied ones by a tie-breaking policy
+pub(crate) fn tie_step(tied: Vec<String>, worst: &[String]) -> CountStep {
+    let others: Vec<String> = tied
+        .iter()
+        .filter(|c| !worst.contains(c))
+        .cloned()
+        .collect();
+    let ranking = if others.is_empty() {
+        vec![tied.clone()]
+    } else {
+        vec![others, worst.to_vec()]
+    };
+
+    CountStep::TieBroken { tied, ranking }
+}
+
+impl fmt::Display for CountStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CountStep::Scores { label, scores } => {
+                let ordered: Vec<String> = rank_by_scores(scores)
+                    .into_iter()
+                    .flatten()
+                    .map(|c| format!("{} ({})", c, format_score(scores[&c])))
+                    .collect();
+                write!(f, "{}: {}", label, ordered.join(", "))
+            }
+            CountStep::CondorcetWinner(c) => {
+                write!(f, "{} beats every other candidate one-to-one", c)
+            }
+            CountStep::NoCondorcetWinner { smith_set, cycle } => match cycle {
+                Some(cycle) => write!(
+                    f,
+                    "No candidate beats every other one because {} > {}",
+                    cycle.join(" > "),
+                    cycle[0]
+                ),
+                None => write!(
+                    f,
+                    "No candidate beats every other one among {}",
+                    smith_set.join(", ")
+                ),
+            },
+            CountStep::Duel(victory) => write!(f, "One-to-one: {}", victory),
+            CountStep::Locked(victory) => write!(f, "Locked: {}", victory),
+            CountStep::Skipped(victory) => {
+                write!(f, "Skipped because it would create a cycle: {}", victory)
+            }
+            CountStep::StrongestPath {
+                winner,
+                loser,
+                strength,
+                opposite,
+            } => write!(
+                f,
+                "Strongest paths: {} > {} ({} to {})",
+                winner, loser, strength, opposite
+            ),
+            CountStep::MedianGrades { removed, grades } => {
+                let grades: Vec<String> = grades
+                    .iter()
+                    .map(|(c, grade)| format!("{} ({})", c, grade))
+                    .collect();
+                match removed {
+                    0 => write!(f, "Median grades: {}", grades.join(", ")),
+                    _ => write!(
+                        f,
+                        "Median grades, round {}: {}",
+                        removed + 1,
+                        grades.join(", ")
+                    ),
+                }
+            }
+            CountStep::Elected(elected) => write!(f, "Elected: {}", elected.join(", ")),
+            CountStep::SurplusTransferred {
+                candidate,
+                surplus,
+                ratio,
+            } => write!(
+                f,
+                "Surplus of {} votes of {} transferred, ballots keep {} of their value",
+                format_score(*surplus),
+                candidate,
+                format_score(*ratio)
+            ),
+            CountStep::Eliminated(eliminated) => {
+                write!(f, "Eliminated: {}", eliminated.join(", "))
+            }
+            CountStep::TieBroken { tied, ranking } => {
+                if ranking.len() > 1 {
+                    let ranking: Vec<String> =
+                        ranking.iter().map(|rank| rank.join(" = ")).collect();
+                    write!(
+                        f,
+                        "Tie between {} broken: {}",
+                        tied.join(", "),
+                        ranking.join(" > ")
+                    )
+                } else {
+                    write!(f, "Tie between {} kept", tied.join(", "))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::voting_system::{
+        BlackMethod, MajorityJudgment, RankedPairs, Schulze, SingleBallot, VotingSystem,
+    };
+
+    /// Ballots with the cycle A > B > C > A
+    fn cycle<V: VotingSystem>(mut vote: V) -> V {
+        for (n, v) in [(7, "ABC"), (5, "BCA"), (4, "CAB")] {
+            for _ in 0..n {
+                vote.vote(SingleBallot::Ranked(
+                    v.chars().map(|c| c.to_string()).collect(),
+                ))
+                .unwrap();
+            }
+        }
+        vote
+    }
+
+    #[test]
+    fn black_method_explanation() {
+        let mut b = BlackMethod::new(&["A", "B", "C"]);
+
+        for (n, v) in [(7, "ABC"), (5, "BCA"), (4, "CAB")] {
+            for _ in 0..n {
+                b.vote(SingleBallot::Ranked(
+                    v.chars().map(|c| c.to_string()).collect(),
+                ))
+                .unwrap();
+            }
+        }
+
+        let result = b.result();
+        let explanation = result.get_explanation();
+        assert_eq!(
+            "No candidate beats every other one because A > B > C > A",
+            explanation[0].to_string()
+        );
+        assert_eq!(
+            "Borda count: A (18), B (17), C (13)",
+            explanation[1].to_string()
+        );
+    }
+
+    #[test]
+    fn ranked_pairs_explanation() {
+        let result = cycle(RankedPairs::new(&["A", "B", "C"])).result();
+        let explanation: Vec<String> = result
+            .get_explanation()
+            .iter()
+            .map(|step| step.to_string())
+            .collect();
+
+        assert_eq!(
+            vec![
+                "Locked: B > C (12 to 4)",
+                "Locked: A > B (11 to 5)",
+                "Skipped because it would create a cycle: C > A (9 to 7)",
+                "Candidates beaten through locked victories: A (2), B (1), C (0)",
+            ],
+            explanation
+        );
+    }
+
+    #[test]
+    fn schulze_explanation() {
+        let result = cycle(Schulze::new(&["A", "B", "C"])).result();
+        let explanation: Vec<String> = result
+            .get_explanation()
+            .iter()
+            .map(|step| step.to_string())
+            .collect();
+
+        // The path from B to A goes through C > A, weaker than A > B
+        assert_eq!(
+            vec![
+                "Strongest paths: A > B (11 to 9)",
+                "Strongest paths: A > C (11 to 9)",
+                "Strongest paths: B > C (12 to 9)",
+                "Candidates beaten through the strongest paths: A (2), B (1), C (0)",
+            ],
+            explanation
+        );
+    }
+
+    #[test]
+    fn majority_judgment_explanation() {
+        let mut m = MajorityJudgment::new(&["A", "B", "C"]);
+
+        for (a, b, c) in [(0, 1, 4), (2, 2, 5), (3, 2, 5)] {
+            m.vote(SingleBallot::Graded(HashMap::from([
+                ("A".to_string(), a),
+                ("B".to_string(), b),
+                ("C".to_string(), c),
+            ])))
+            .unwrap();
+        }
+
+        // Only A and B are still compared once their median grades are equal
+        let result = m.result();
+        let explanation: Vec<String> = result
+            .get_explanation()
+            .iter()
+            .map(|step| step.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "Median grades: B (Good), A (Good), C (Reject)",
+                "Median grades, round 2: B (Good), A (Fair)",
+            ],
+            explanation
+        );
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+
+use crate::voting_system::definition::*;
+use crate::voting_system::explanation::tie_step;
+use crate::voting_system::result::rank_by_scores;
+use crate::voting_system::{CountStep, ElectionResult};
+
+/// # Instant-runoff voting
+/// Each round, ballots count for their most preferred remaining candidate.
+/// A candidate with a majority of these votes wins,
+/// otherwise the candidate with the fewest votes is eliminated
+/// and its ballots are transferred to the next preference.
+/// A ballot ranking several remaining candidates equally is split between them.
+/// Candidates tied for elimination are eliminated together when they have fewer votes
+/// together than any other candidate, otherwise the tie-breaking policy is used,
+/// and when it declares the tie every candidate who can win is a tied winner.
+/// See [IRV_wikipedia].
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::voting_system::{InstantRunoff, VotingSystem, SingleBallot};
+///
+/// let mut irv = InstantRunoff::new(&["A", "B", "C"]);
+///
+/// for _ in 0..2 {
+///     irv.vote(SingleBallot::Ranked(vec![
+///         "A".to_string(),
+///         "B".to_string(),
+///         "C".to_string(),
+///     ])).unwrap();
+///     irv.vote(SingleBallot::Ranked(vec![
+///         "B".to_string(),
+///         "A".to_string(),
+///         "C".to_string(),
+///     ])).unwrap();
+/// }
+/// irv.vote(SingleBallot::Ranked(vec![
+///     "C".to_string(),
+///     "B".to_string(),
+///     "A".to_string(),
+/// ])).unwrap();
+///
+/// assert_eq!(vec!["B".to_string()], irv.result().get_winners());
+/// ```
+///
+/// [IRV_wikipedia]: https://en.wikipedia.org/wiki/Instant-runoff_voting
+pub struct InstantRunoff(VotingSystemInfo<RankedBallots>);
+
+/// Count the ballots for their most preferred remaining candidate,
+/// equally ranked candidates sharing the ballot
+fn count_round(ballot_box: &RankedBallots, hopeful: &[String]) -> HashMap<String, f64> {
+    let mut count: HashMap<String, f64> = hopeful.iter().map(|c| (c.to_owned(), 0.)).collect();
+    for (b, w) in ballot_box.ballots.iter().zip(&ballot_box.weights) {
+        let preferred = RankedBallots::preferred(b, hopeful);
+        for c in &preferred {
+            count
+                .entry(c.to_string())
+                .and_modify(|v| *v += *w as f64 / preferred.len() as f64);
+        }
+    }
+    count
+}
+
+/// Check if tied candidates can be eliminated together without changing the winner,
+/// which is the case when they have fewer votes together than any other candidate
+pub(crate) fn can_eliminate_together(count: &HashMap<String, f64>, lowest: &[String]) -> bool {
+    let together: f64 = lowest.iter().map(|c| count[c]).sum();
+    count
+        .iter()
+        .filter(|(c, _)| !lowest.contains(c))
+        .all(|(_, v)| together < *v)
+}
+
+/// Find every candidate who wins for one of the ways to eliminate tied candidates
+fn possible_winners(ballot_box: &RankedBallots, mut hopeful: Vec<String>) -> Vec<String> {
+    loop {
+        let count = count_round(ballot_box, &hopeful);
+        let total: f64 = count.values().sum();
+        if let Some(winner) = hopeful.iter().find(|c| 2. * count[*c] > total) {
+            return vec![winner.to_owned()];
+        }
+
+        let fewest = count.values().cloned().fold(f64::INFINITY, f64::min);
+        let lowest: Vec<String> = hopeful
+            .iter()
+            .filter(|c| count[*c] == fewest)
+            .cloned()
+            .collect();
+        if lowest.len() == hopeful.len() {
+            hopeful.sort();
+            return hopeful;
+        }
+
+        if lowest.len() > 1 && !can_eliminate_together(&count, &lowest) {
+            let mut winners: Vec<String> = lowest
+                .iter()
+                .flat_map(|c| {
+                    possible_winners(
+                        ballot_box,
+                        hopeful.iter().filter(|h| *h != c).cloned().collect(),
+                    )
+                })
+                .collect();
+            winners.sort();
+            winners.dedup();
+            return winners;
+        }
+        hopeful.retain(|c| !lowest.contains(c));
+    }
+}
+
+impl VotingSystem for InstantRunoff {
+    type B = RankedBallots;
+
+    const NAME: &str = "irv";
+    const LONG_NAME: &str = "Instant-runoff voting";
+
+    fn with_parameters(choices: &[&str], parameters: VotingParameters) -> Self {
+        Self(VotingSystemInfo::new(
+            Self::LONG_NAME,
+            BallotForm::Ranked,
+            choices,
+            parameters,
+        ))
+    }
+
+    fn get_info(&self) -> &VotingSystemInfo<Self::B> {
+        &self.0
+    }
+
+    fn get_mut_info(&mut self) -> &mut VotingSystemInfo<Self::B> {
+        &mut self.0
+    }
+
+    fn result(&self) -> ElectionResult {
+        let ballot_box = self.0.get_ballot_box();
+        let tie_break = self.0.get_parameters().get_tie_break();
+
+        let mut hopeful: Vec<String> = ballot_box.choices.clone();
+        let mut eliminated: Vec<Vec<String>> = Vec::new();
+        let mut rounds: Vec<HashMap<String, f64>> = Vec::new();
+        let mut scores: HashMap<String, f64> = HashMap::new();
+        let mut tie = false;
+        let mut tied_winners: Option<Vec<String>> = None;
+        let mut steps = Vec::new();
+
+        loop {
+            // Count the most preferred remaining candidate of each ballot
+            let count = count_round(ballot_box, &hopeful);
+            count.iter().for_each(|(c, v)| {
+                scores.insert(c.to_owned(), *v);
+            });
+            steps.push(CountStep::Scores {
+                label: format!("Round {}", rounds.len() + 1),
+                scores: count.clone(),
+            });
+            rounds.push(count);
+
+            let count = rounds.last().unwrap();
+            let total: f64 = count.values().sum();
+            if hopeful.len() <= 1 || count.values().any(|v| 2. * v > total) {
+                break;
+            }
+
+            // Eliminate the candidates with the fewest votes
+            let fewest = count.values().cloned().fold(f64::INFINITY, f64::min);
+            let lowest: Vec<String> = hopeful
+                .iter()
+                .filter(|c| count[*c] == fewest)
+                .cloned()
+                .collect();
+            let lowest = if lowest.len() > 1 {
+                tie = true;
+                let worst = tie_break.worst(&lowest, &rounds[..rounds.len() - 1]);
+                steps.push(tie_step(lowest, &worst));
+                worst
+            } else {
+                lowest
+            };
+
+            if lowest.len() == hopeful.len() {
+                break;
+            }
+            if lowest.len() > 1 && !can_eliminate_together(count, &lowest) {
+                // The winner depends on which tied candidate is eliminated
+                tied_winners = Some(possible_winners(ballot_box, hopeful.clone()));
+                break;
+            }
+            hopeful.retain(|c| !lowest.contains(c));
+            steps.push(CountStep::Eliminated(lowest.clone()));
+            eliminated.push(lowest);
+        }
+
+        // Remaining candidates first, then eliminated ones from the last to the first
+        let last_round = rounds.pop().unwrap();
+        let mut ranking = match tied_winners {
+            Some(winners) => {
+                let others: HashMap<String, f64> = last_round
+                    .into_iter()
+                    .filter(|(c, _)| !winners.contains(c))
+                    .collect();
+                let mut ranking = vec![winners];
+                ranking.extend(rank_by_scores(&others));
+                ranking
+            }
+            None => rank_by_scores(&last_round),
+        };
+        ranking.extend(eliminated.into_iter().rev());
+
+        ElectionResult::new(Self::LONG_NAME, ranking, scores, self.0.get_count())
+            .with_explanation(steps)
+            .with_tie(tie)
+            .break_ties(tie_break, &rounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voting_system::TieBreak;
+
+    fn vote(irv: &mut InstantRunoff, ballots: Vec<(usize, Vec<&str>)>) {
+        for (n, v) in ballots {
+            for _ in 0..n {
+                irv.vote(SingleBallot::Ranked(
+                    v.iter().map(|s| s.to_string()).collect(),
+                ))
+                .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn instant_runoff() {
+        let mut irv = InstantRunoff::new(&["A", "B", "C", "D"]);
+
+        vote(
+            &mut irv,
+            vec![
+                (5, vec!["A", "B", "C", "D"]),
+                (4, vec!["B", "C", "A", "D"]),
+                (3, vec!["C", "B", "A", "D"]),
+                (1, vec!["D", "B", "C", "A"]),
+            ],
+        );
+
+        // D then C are eliminated, their ballots go to B
+        let result = irv.result();
+        assert_eq!(
+            &vec![
+                vec!["B".to_string()],
+                vec!["A".to_string()],
+                vec!["C".to_string()],
+                vec!["D".to_string()]
+            ],
+            result.get_ranking()
+        );
+        assert_eq!(Some(8.), result.get_score("B"));
+        assert!(!result.is_tie());
+    }
+
+    #[test]
+    fn instant_runoff_elimination_tie() {
+        let ballots = vec![
+            (3, vec!["A", "B", "C"]),
+            (2, vec!["B", "A", "C"]),
+            (2, vec!["C", "B", "A"]),
+        ];
+
+        // B and C are tied for elimination, A wins if B is eliminated and B if C is
+        let mut irv = InstantRunoff::new(&["A", "B", "C"]);
+        vote(&mut irv, ballots.clone());
+        let result = irv.result();
+        assert!(result.is_tie());
+        assert_eq!(vec!["A".to_string(), "B".to_string()], result.get_winners());
+
+        let parameters = VotingParameters::default()
+            .with_tie_break(TieBreak::Priority(vec!["B".to_string(), "C".to_string()]));
+        let mut irv = InstantRunoff::with_parameters(&["A", "B", "C"], parameters);
+        vote(&mut irv, ballots);
+        let result = irv.result();
+        assert!(result.is_tie());
+        assert_eq!(vec!["B".to_string()], result.get_winners());
+
+        // C and D have fewer votes together than B, so they are eliminated together
+        let mut irv = InstantRunoff::new(&["A", "B", "C", "D"]);
+        vote(
+            &mut irv,
+            vec![
+                (4, vec!["A", "B", "C", "D"]),
+                (3, vec!["B", "A", "C", "D"]),
+                (1, vec!["C", "B", "A", "D"]),
+                (1, vec!["D", "B", "C", "A"]),
+            ],
+        );
+        let result = irv.result();
+        assert_eq!(vec!["B".to_string()], result.get_winners());
+        assert_eq!(
+            Some(&vec!["C".to_string(), "D".to_string()]),
+            result.get_ranking().last()
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use crate::voting_system::definition::*;
+use crate::voting_system::result::lower_median;
+use crate::voting_system::{CountStep, ElectionResult, ResultDetails};
+
+/// # Majority judgment
+/// This method was proposed by Michel Balinski and Rida Laraki in 2007.
+/// Each voter gives a grade to every candidate
+/// and candidates are ranked by their median grade.
+/// When several candidates have the same median grade,
+/// one median grade is removed from each of them and the new median grades are compared,
+/// until a difference appears.
+/// With an even number of ballots, the lower of the two middle grades is used.
+/// See [MajorityJudgment_wikipedia].
+///
+/// Here an exemple :
+/// ```rust
+/// use std::collections::HashMap;
+/// use libvotally::voting_system::{MajorityJudgment, VotingSystem, SingleBallot};
+///
+/// let mut m = MajorityJudgment::new(&["A", "B"]);
+///
+/// // 0 is the best grade: Excellent, Very good, Good, Fair, Poor, Reject
+/// for (a, b) in [(0, 1), (2, 2), (3, 2)] {
+///     m.vote(SingleBallot::Graded(HashMap::from([
+///         ("A".to_string(), a),
+///         ("B".to_string(), b),
+///     ]))).unwrap();
+/// }
+///
+/// // Both medians are Good, then B's next median is still Good but A's is Fair
+/// assert_eq!(vec!["B".to_string()], m.result().get_winners());
+/// ```
+///
+/// [MajorityJudgment_wikipedia]: https://en.wikipedia.org/wiki/Majority_judgment
+pub struct MajorityJudgment(VotingSystemInfo<GradeBallots>);
+
+/// Get the successive median grades, removing the median grade each time
+fn median_sequence(distribution: &[u64]) -> Vec<usize> {
+    let mut distribution = distribution.to_vec();
+    let mut sequence = Vec::new();
+
+    while let Some(m) = lower_median(&distribution) {
+        distribution[m] -= 1;
+        sequence.push(m);
+    }
+
+    sequence
+}
+
+impl VotingSystem for MajorityJudgment {
+    type B = GradeBallots;
+
+    const NAME: &str = "majority-judgment";
+    const LONG_NAME: &str = "Majority judgment";
+
+    fn with_parameters(choices: &[&str], parameters: VotingParameters) -> Self {
+        Self(VotingSystemInfo::new(
+            Self::LONG_NAME,
+            BallotForm::Graded,
+            choices,
+            parameters,
+        ))
+    }
+
+    fn get_info(&self) -> &VotingSystemInfo<Self::B> {
+        &self.0
+    }
+
+    fn get_mut_info(&mut self) -> &mut VotingSystemInfo<Self::B> {
+        &mut self.0
+    }
+
+    fn result(&self) -> ElectionResult {
+        let GradeBallots(g) = self.0.get_ballot_box();
+        let grades = self.0.get_parameters().get_grades();
+
+        let distributions: HashMap<String, Vec<u64>> = g
+            .iter()
+            .map(|(c, d)| {
+                let mut d = d.clone();
+                d.resize(grades.len().max(d.len()), 0);
+                (c.to_owned(), d)
+            })
+            .collect();
+
+        // Lower median sequences are compared, the smallest grade index is the best
+        let mut sequences: Vec<(&String, Vec<usize>)> = distributions
+            .iter()
+            .map(|(c, d)| (c, median_sequence(d)))
+            .collect();
+        sequences.sort_by(|(c1, s1), (c2, s2)| s1.cmp(s2).then(c1.cmp(c2)));
+
+        let mut ranking: Vec<Vec<String>> = Vec::new();
+        for (i, (c, s)) in sequences.iter().enumerate() {
+            match ranking.last_mut() {
+                Some(rank) if i > 0 && sequences[i - 1].1 == *s => rank.push(c.to_string()),
+                _ => ranking.push(vec![c.to_string()]),
+            }
+        }
+
+        // Median grades of every candidate,
+        // then the next ones of the candidates tied so far with another one
+        // until a difference appears
+        let mut steps = Vec::new();
+        for removed in 0.. {
+            let compared: Vec<(String, String)> = sequences
+                .iter()
+                .filter(|(_, s)| {
+                    s.len() > removed
+                        && (removed == 0
+                            || sequences
+                                .iter()
+                                .any(|(_, s2)| s2 != s && s2.starts_with(&s[..removed])))
+                })
+                .map(|(c, s)| (c.to_string(), grades[s[removed]].to_owned()))
+                .collect();
+            if compared.is_empty() {
+                break;
+            }
+            steps.push(CountStep::MedianGrades {
+                removed,
+                grades: compared,
+            });
+        }
+
+        // The score of a candidate is the number of grades below its median grade
+        let scores: HashMap<String, f64> = distributions
+            .iter()
+            .filter_map(|(c, d)| {
+                lower_median(d).map(|m| (c.to_owned(), (grades.len() - 1 - m) as f64))
+            })
+            .collect();
+
+        ElectionResult::new(Self::LONG_NAME, ranking, scores, self.0.get_count())
+            .with_explanation(steps)
+            .break_ties(self.0.get_parameters().get_tie_break(), &[])
+            .with_details(ResultDetails::Grades {
+                grades: grades.clone(),
+                distributions,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn majority_judgment() {
+        let mut m = MajorityJudgment::with_parameters(
+            &["A", "B", "C"],
+            VotingParameters::default().with_grades(
+                ["Good", "Fair", "Poor"]
+                    .iter()
+                    .map(|g| g.to_string())
+                    .collect(),
+            ),
+        );
+
+        for (n, [a, b, c]) in [(2, [0, 1, 2]), (1, [2, 1, 0]), (1, [2, 0, 2])] {
+            for _ in 0..n {
+                m.vote(SingleBallot::Graded(HashMap::from([
+                    ("A".to_string(), a),
+                    ("B".to_string(), b),
+                    ("C".to_string(), c),
+                ])))
+                .unwrap();
+            }
+        }
+
+        // B has the best median, Fair, then A and C have the median Poor
+        // but A's next median is Good while C's one stays Poor
+        let result = m.result();
+        assert_eq!(
+            &vec![
+                vec!["B".to_string()],
+                vec!["A".to_string()],
+                vec!["C".to_string()]
+            ],
+            result.get_ranking()
+        );
+        assert_eq!(Some(0.), result.get_score("A"));
+        match result.get_details() {
+            Some(ResultDetails::Grades { distributions, .. }) => {
+                assert_eq!(vec![2, 0, 2], distributions["A"]);
+            }
+            _ => panic!("missing grade distributions"),
+        }
+    }
+}
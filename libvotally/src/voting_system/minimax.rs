@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use crate::voting_system::definition::*;
+use crate::voting_system::explanation::condorcet_step;
+use crate::voting_system::result::{move_first, rank_by_scores};
+use crate::voting_system::{CountStep, ElectionResult, PairwiseMatrix};
+
+/// # Minimax method
+/// The winner is the candidate whose worst pairwise defeat is the smallest,
+/// defeats being measured by their margin.
+/// A candidate without any defeat, such as a Condorcet winner, has a worst defeat of 0.
+/// Scores are the opposite of the worst defeats.
+/// See [Minimax_wikipedia].
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::voting_system::{Minimax, VotingSystem, SingleBallot};
+///
+/// let mut m = Minimax::new(&["A", "B", "C"]);
+///
+/// m.vote(SingleBallot::Ranked(vec![
+///     "A".to_string(),
+///     "B".to_string(),
+///     "C".to_string(),
+/// ])).unwrap();
+/// m.vote(SingleBallot::Ranked(vec![
+///     "B".to_string(),
+///     "A".to_string(),
+///     "C".to_string(),
+/// ])).unwrap();
+/// m.vote(SingleBallot::Ranked(vec![
+///     "C".to_string(),
+///     "A".to_string(),
+///     "B".to_string(),
+/// ])).unwrap();
+///
+/// assert_eq!(vec!["A".to_string()], m.result().get_winners());
+/// ```
+///
+/// [Minimax_wikipedia]: https://en.wikipedia.org/wiki/Minimax_Condorcet_method
+pub struct Minimax(VotingSystemInfo<BattleBallots>);
+
+impl VotingSystem for Minimax {
+    type B = BattleBallots;
+
+    const NAME: &str = "minimax";
+    const LONG_NAME: &str = "Minimax method";
+
+    fn with_parameters(choices: &[&str], parameters: VotingParameters) -> Self {
+        Self(VotingSystemInfo::new(
+            Self::LONG_NAME,
+            BallotForm::Ranked,
+            choices,
+            parameters,
+        ))
+    }
+
+    fn get_info(&self) -> &VotingSystemInfo<Self::B> {
+        &self.0
+    }
+
+    fn get_mut_info(&mut self) -> &mut VotingSystemInfo<Self::B> {
+        &mut self.0
+    }
+
+    fn result(&self) -> ElectionResult {
+        let b = self.0.get_ballot_box();
+
+        let scores: HashMap<String, f64> = b
+            .choices()
+            .map(|c1| {
+                let worst_defeat = b
+                    .choices()
+                    .map(|c2| b.duel(c2, c1) - b.duel(c1, c2))
+                    .max()
+                    .unwrap_or(0)
+                    .max(0);
+                (c1.to_owned(), -worst_defeat as f64)
+            })
+            .collect();
+        let mut ranking = rank_by_scores(&scores);
+
+        // Condorcet winner ?
+        if let Some(c) = b.condorcet_winner() {
+            move_first(&mut ranking, c);
+        }
+
+        // Worst defeat of each candidate, in ranking order
+        let matrix = PairwiseMatrix::from(b);
+        let mut steps = vec![condorcet_step(&matrix)];
+        let victories = matrix.victories();
+        for c in ranking.iter().flatten() {
+            let worst = victories
+                .iter()
+                .filter(|v| &v.loser == c)
+                .max_by_key(|v| (v.margin(), std::cmp::Reverse(&v.winner)));
+            if let Some(worst) = worst {
+                steps.push(CountStep::Duel(worst.clone()));
+            }
+        }
+        steps.push(CountStep::Scores {
+            label: "Opposite of the worst defeat margin".to_string(),
+            scores: scores.clone(),
+        });
+
+        ElectionResult::new(Self::LONG_NAME, ranking, scores, self.0.get_count())
+            .with_explanation(steps)
+            .break_ties(self.0.get_parameters().get_tie_break(), &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimax_method() {
+        let mut m = Minimax::new(&["A", "B", "C"]);
+
+        // A > B by 6, B > C by 8, C > A by 2
+        for (n, v) in [(7, "ABC"), (5, "BCA"), (4, "CAB")] {
+            for _ in 0..n {
+                m.vote(SingleBallot::Ranked(
+                    v.chars().map(|c| c.to_string()).collect(),
+                ))
+                .unwrap();
+            }
+        }
+
+        let result = m.result();
+        assert_eq!(
+            &vec![
+                vec!["A".to_string()],
+                vec!["B".to_string()],
+                vec!["C".to_string()]
+            ],
+            result.get_ranking()
+        );
+        assert_eq!(Some(-2.), result.get_score("A"));
+    }
+}
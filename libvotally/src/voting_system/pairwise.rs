@@ -0,0 +1,293 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use crate::voting_system::PairwiseVictory;
+use crate::voting_system::definition::*;
+
+/// Number of ballots preferring each candidate to each other one
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::voting_system::{PairwiseMatrix, RawBallots, SingleBallot};
+///
+/// let ballots: Vec<SingleBallot> = ["ABC", "BAC", "CAB", "ABC"]
+///     .iter()
+///     .map(|v| SingleBallot::Ranked(v.chars().map(|c| c.to_string()).collect()))
+///     .collect();
+/// let matrix =
+///     PairwiseMatrix::from_ballots(&RawBallots::from_ballots(&["A", "B", "C"], ballots)).unwrap();
+///
+/// assert_eq!(3, matrix.votes("A", "B"));
+/// assert_eq!(2, matrix.margin("A", "B"));
+/// assert_eq!(Some(&"A".to_string()), matrix.condorcet_winner());
+/// assert_eq!(vec!["A".to_string()], matrix.smith_set());
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PairwiseMatrix {
+    /// Candidates sorted by name
+    choices: Vec<String>,
+    /// `votes[i][j]` is the number of ballots preferring choices[i] to choices[j]
+    votes: Vec<Vec<i64>>,
+}
+
+impl PairwiseMatrix {
+    /// Count ranked or scored ballots
+    pub fn from_ballots(ballots: &RawBallots) -> Result<Self, InvalidBallot> {
+        let choices: Vec<&str> = ballots.get_choices().iter().map(|c| c.as_str()).collect();
+        let mut battles = BattleBallots::new(&choices);
+
+        for b in ballots.get_ballots() {
+            battles.vote_weighted(b.clone())?;
+        }
+
+        Ok(Self::from(&battles))
+    }
+
+    /// Get all candidates sorted by name
+    pub fn get_choices(&self) -> &[String] {
+        &self.choices
+    }
+
+    fn index(&self, c: &str) -> Option<usize> {
+        self.choices.iter().position(|x| x == c)
+    }
+
+    /// Get the number of ballots preferring c1 to c2
+    pub fn votes(&self, c1: &str, c2: &str) -> i64 {
+        match (self.index(c1), self.index(c2)) {
+            (Some(i), Some(j)) => self.votes[i][j],
+            _ => 0,
+        }
+    }
+
+    /// Get the number of ballots preferring c1 to c2 minus the number preferring c2 to c1
+    pub fn margin(&self, c1: &str, c2: &str) -> i64 {
+        self.votes(c1, c2) - self.votes(c2, c1)
+    }
+
+    /// Get the share of ballots preferring c1 among ballots with a preference between c1 and c2
+    pub fn ratio(&self, c1: &str, c2: &str) -> Option<f64> {
+        let total = self.votes(c1, c2) + self.votes(c2, c1);
+        (total > 0).then(|| self.votes(c1, c2) as f64 / total as f64)
+    }
+
+    /// Return true if more ballots prefer c1 to c2 than c2 to c1
+    pub fn beats(&self, c1: &str, c2: &str) -> bool {
+        self.margin(c1, c2) > 0
+    }
+
+    /// Get every pairwise victory
+    pub fn victories(&self) -> Vec<PairwiseVictory> {
+        let mut victories = Vec::new();
+        for c1 in &self.choices {
+            for c2 in &self.choices {
+                if self.beats(c1, c2) {
+                    victories.push(PairwiseVictory {
+                        winner: c1.to_owned(),
+                        loser: c2.to_owned(),
+                        winning_votes: self.votes(c1, c2),
+                        losing_votes: self.votes(c2, c1),
+                    });
+                }
+            }
+        }
+        victories
+    }
+
+    /// Get the candidate beating every other one, if it exists
+    pub fn condorcet_winner(&self) -> Option<&String> {
+        self.choices.iter().find(|c1| {
+            self.choices
+                .iter()
+                .all(|c2| c1 == &c2 || self.beats(c1, c2))
+        })
+    }
+
+    /// Get the candidate beaten by every other one, if it exists
+    pub fn condorcet_loser(&self) -> Option<&String> {
+        self.choices.iter().find(|c1| {
+            self.choices
+                .iter()
+                .all(|c2| c1 == &c2 || self.beats(c2, c1))
+        })
+    }
+
+    /// Transitive closure of a relation between candidates
+    fn closure(&self, relation: impl Fn(&str, &str) -> bool) -> Vec<Vec<bool>> {
+        let n = self.choices.len();
+        let mut reach: Vec<Vec<bool>> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| i == j || relation(&self.choices[i], &self.choices[j]))
+                    .collect()
+            })
+            .collect();
+
+        // Floyd-Warshall algorithm
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    reach[i][j] |= reach[i][k] && reach[k][j];
+                }
+            }
+        }
+
+        reach
+    }
+
+    /// Get the smallest set of candidates beating every candidate outside it
+    pub fn smith_set(&self) -> Vec<String> {
+        // Candidates reaching every other one by beating or tying
+        let reach = self.closure(|c1, c2| !self.beats(c2, c1));
+
+        self.choices
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| reach[*i].iter().all(|r| *r))
+            .map(|(_, c)| c.to_owned())
+            .collect()
+    }
+
+    /// Get a shortest cycle of pairwise victories, each candidate beating the next one
+    /// and the last one beating the first one
+    pub fn find_cycle(&self) -> Option<Vec<String>> {
+        let mut shortest: Option<Vec<String>> = None;
+
+        for start in &self.choices {
+            // Breadth-first search of a path going back to start
+            let mut previous: HashMap<&String, &String> = HashMap::new();
+            let mut queue = VecDeque::from([start]);
+
+            while let Some(c1) = queue.pop_front() {
+                if self.beats(c1, start) {
+                    let mut cycle = vec![c1.to_owned()];
+                    let mut c = c1;
+                    while c != start {
+                        c = previous[c];
+                        cycle.push(c.to_owned());
+                    }
+                    cycle.reverse();
+
+                    if shortest.as_ref().is_none_or(|s| cycle.len() < s.len()) {
+                        shortest = Some(cycle);
+                    }
+                    break;
+                }
+
+                for c2 in &self.choices {
+                    if c2 != start && !previous.contains_key(c2) && self.beats(c1, c2) {
+                        previous.insert(c2, c1);
+                        queue.push_back(c2);
+                    }
+                }
+            }
+        }
+
+        shortest
+    }
+
+    /// Get the union of the smallest sets of candidates unbeaten by any candidate outside them
+    pub fn schwartz_set(&self) -> Vec<String> {
+        // Candidates reaching back every candidate which reaches them by beating
+        let reach = self.closure(|c1, c2| self.beats(c1, c2));
+        let n = self.choices.len();
+
+        self.choices
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (0..n).all(|j| !reach[j][*i] || reach[*i][j]))
+            .map(|(_, c)| c.to_owned())
+            .collect()
+    }
+}
+
+impl From<&BattleBallots> for PairwiseMatrix {
+    fn from(battles: &BattleBallots) -> Self {
+        let mut choices: Vec<String> = battles.choices().cloned().collect();
+        choices.sort();
+
+        let votes = choices
+            .iter()
+            .map(|c1| choices.iter().map(|c2| battles.duel(c1, c2)).collect())
+            .collect();
+
+        Self { choices, votes }
+    }
+}
+
+impl fmt::Display for PairwiseMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self
+            .choices
+            .iter()
+            .map(|c| c.chars().count())
+            .chain(self.votes.iter().flatten().map(|v| v.to_string().len()))
+            .max()
+            .unwrap_or(0);
+
+        write!(f, "{:width$}", "")?;
+        for c in &self.choices {
+            write!(f, " | {:width$}", c)?;
+        }
+        for (c1, row) in self.choices.iter().zip(&self.votes) {
+            write!(f, "\n{:width$}", c1)?;
+            for (c2, v) in self.choices.iter().zip(row) {
+                if c1 == c2 {
+                    write!(f, " | {:width$}", "-")?;
+                } else {
+                    write!(f, " | {:width$}", v)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smith_and_schwartz_sets() {
+        // A > B > C > A is a cycle above D, which is beaten by everyone
+        let mut battles = BattleBallots::new(&["A", "B", "C", "D"]);
+        for v in ["ABCD", "BCAD", "CABD"] {
+            battles
+                .vote(SingleBallot::Ranked(
+                    v.chars().map(|c| c.to_string()).collect(),
+                ))
+                .unwrap();
+        }
+
+        let matrix = PairwiseMatrix::from(&battles);
+        assert_eq!(None, matrix.condorcet_winner());
+        assert_eq!(Some(&"D".to_string()), matrix.condorcet_loser());
+        assert_eq!(Some(2. / 3.), matrix.ratio("A", "B"));
+        assert_eq!(6, matrix.victories().len());
+
+        let cycle = vec!["A".to_string(), "B".to_string(), "C".to_string()];
+        assert_eq!(Some(cycle.clone()), matrix.find_cycle());
+        assert_eq!(cycle, matrix.smith_set());
+        assert_eq!(cycle, matrix.schwartz_set());
+
+        // A ties with B, B beats C and C beats A: only B is unbeaten
+        let mut battles = BattleBallots::new(&["A", "B", "C"]);
+        for v in ["BCA", "CAB", "ABC", "BCA"] {
+            battles
+                .vote(SingleBallot::Ranked(
+                    v.chars().map(|c| c.to_string()).collect(),
+                ))
+                .unwrap();
+        }
+
+        let matrix = PairwiseMatrix::from(&battles);
+        assert_eq!(None, matrix.condorcet_winner());
+        assert_eq!(
+            vec!["A".to_string(), "B".to_string(), "C".to_string()],
+            matrix.smith_set()
+        );
+        assert_eq!(vec!["B".to_string()], matrix.schwartz_set());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::voting_system::ElectionResult;
 use crate::voting_system::definition::*;
 
 /// # First-past-the-post voting
@@ -6,14 +7,14 @@ use crate::voting_system::definition::*;
 /// ```rust
 /// use libvotally::voting_system::{Plurality, VotingSystem, SingleBallot};
 ///
-/// let mut p = Plurality::new(&vec!["A", "B", "C"]);
+/// let mut p = Plurality::new(&["A", "B", "C"]);
 ///
 /// p.vote(SingleBallot::Uninominal("A".to_string()));
 /// p.vote(SingleBallot::Uninominal("B".to_string()));
 /// p.vote(SingleBallot::Uninominal("C".to_string()));
 /// p.vote(SingleBallot::Uninominal("A".to_string()));
 ///
-/// assert_eq!("A", p.result());
+/// assert_eq!(vec!["A".to_string()], p.result().get_winners());
 /// ```
 pub struct Plurality(VotingSystemInfo<PointBallots>);
 
@@ -23,11 +24,12 @@ impl VotingSystem for Plurality {
     const NAME: &str = "plurality";
     const LONG_NAME: &str = "Plurality voting";
 
-    fn new(choices: &Vec<&str>) -> Self {
+    fn with_parameters(choices: &[&str], parameters: VotingParameters) -> Self {
         Self(VotingSystemInfo::new(
             Self::LONG_NAME,
             BallotForm::Uninominal,
             choices,
+            parameters,
         ))
     }
 
@@ -39,28 +41,53 @@ impl VotingSystem for Plurality {
         &mut self.0
     }
 
-    fn result(&self) -> String {
+    fn result(&self) -> ElectionResult {
         let PointBallots(c) = self.0.get_ballot_box();
-        c.iter()
-            .max_by(|a, b| a.1.cmp(&b.1))
-            .map(|(k, _v)| k)
-            .cloned()
-            .unwrap()
+        ElectionResult::from_scores(
+            Self::LONG_NAME,
+            c.iter().map(|(k, v)| (k.to_owned(), *v as f64)).collect(),
+            self.0.get_count(),
+        )
+        .break_ties(self.0.get_parameters().get_tie_break(), &[])
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::voting_system::TieBreak;
 
     #[test]
     fn plurality_voting() {
-        let mut p = Plurality::new(&vec!["A", "B", "C"]);
+        let mut p = Plurality::new(&["A", "B", "C"]);
+
+        for v in ["A", "B", "A", "C", "B", "A"] {
+            p.vote(SingleBallot::Uninominal(v.to_string())).unwrap();
+        }
+
+        assert_eq!(vec!["A".to_string()], p.result().get_winners());
+    }
+
+    #[test]
+    fn plurality_tie() {
+        let mut p = Plurality::new(&["A", "B", "C"]);
+        for v in ["A", "B", "C", "B", "A"] {
+            p.vote(SingleBallot::Uninominal(v.to_string())).unwrap();
+        }
+
+        let result = p.result();
+        assert!(result.is_tie());
+        assert_eq!(vec!["A".to_string(), "B".to_string()], result.get_winners());
 
-        for v in vec!["A", "B", "A", "C", "B", "A"] {
+        let parameters = VotingParameters::default()
+            .with_tie_break(TieBreak::Priority(vec!["B".to_string(), "A".to_string()]));
+        let mut p = Plurality::with_parameters(&["A", "B", "C"], parameters);
+        for v in ["A", "B", "C", "B", "A"] {
             p.vote(SingleBallot::Uninominal(v.to_string())).unwrap();
         }
 
-        assert_eq!("A", p.result());
+        let result = p.result();
+        assert!(result.is_tie());
+        assert_eq!(vec!["B".to_string()], result.get_winners());
     }
 }
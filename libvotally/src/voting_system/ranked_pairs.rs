@@ -0,0 +1,183 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::voting_system::definition::*;
+use crate::voting_system::{CountStep, ElectionResult, PairwiseVictory, ResultDetails};
+
+/// # Ranked Pairs
+/// This method was proposed by Nicolaus Tideman in 1987.
+/// Pairwise victories are sorted from the largest margin to the smallest
+/// and locked in one by one, unless they would create a cycle.
+/// See [RankedPairs_wikipedia].
+///
+/// Victories with equal margins are sorted by decreasing number of winning votes,
+/// then by alphabetical order of the winner and then of the loser.
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::voting_system::{RankedPairs, VotingSystem, SingleBallot};
+///
+/// let mut r = RankedPairs::new(&["A", "B", "C"]);
+///
+/// r.vote(SingleBallot::Ranked(vec![
+///     "A".to_string(),
+///     "B".to_string(),
+///     "C".to_string(),
+/// ])).unwrap();
+/// r.vote(SingleBallot::Ranked(vec![
+///     "C".to_string(),
+///     "A".to_string(),
+///     "B".to_string(),
+/// ])).unwrap();
+///
+/// assert_eq!(vec!["A".to_string()], r.result().get_winners());
+/// ```
+///
+/// [RankedPairs_wikipedia]: https://en.wikipedia.org/wiki/Ranked_pairs
+pub struct RankedPairs(VotingSystemInfo<BattleBallots>);
+
+/// Return true if `to` can be reached from `from` through locked victories
+fn reachable(locked: &[PairwiseVictory], from: &String, to: &String) -> bool {
+    let mut visited = HashSet::new();
+    let mut stack = vec![from];
+
+    while let Some(c) = stack.pop() {
+        if c == to {
+            return true;
+        }
+        if visited.insert(c) {
+            stack.extend(locked.iter().filter(|v| &v.winner == c).map(|v| &v.loser));
+        }
+    }
+
+    false
+}
+
+impl VotingSystem for RankedPairs {
+    type B = BattleBallots;
+
+    const NAME: &str = "ranked-pairs";
+    const LONG_NAME: &str = "Ranked Pairs";
+
+    fn with_parameters(choices: &[&str], parameters: VotingParameters) -> Self {
+        Self(VotingSystemInfo::new(
+            Self::LONG_NAME,
+            BallotForm::Ranked,
+            choices,
+            parameters,
+        ))
+    }
+
+    fn get_info(&self) -> &VotingSystemInfo<Self::B> {
+        &self.0
+    }
+
+    fn get_mut_info(&mut self) -> &mut VotingSystemInfo<Self::B> {
+        &mut self.0
+    }
+
+    fn result(&self) -> ElectionResult {
+        let BattleBallots(b) = self.0.get_ballot_box();
+        let ch: Vec<&String> = self.0.get_choices().collect();
+
+        let mut victories: Vec<PairwiseVictory> = b
+            .iter()
+            .filter_map(|((c1, c2), v)| {
+                let opposite = b[&(c2.to_owned(), c1.to_owned())];
+                (*v > opposite).then(|| PairwiseVictory {
+                    winner: c1.to_owned(),
+                    loser: c2.to_owned(),
+                    winning_votes: *v,
+                    losing_votes: opposite,
+                })
+            })
+            .collect();
+        victories.sort_by(|v1, v2| {
+            v2.margin()
+                .cmp(&v1.margin())
+                .then(v2.winning_votes.cmp(&v1.winning_votes))
+                .then(v1.winner.cmp(&v2.winner))
+                .then(v1.loser.cmp(&v2.loser))
+        });
+
+        // Lock in victories which don't create a cycle
+        let mut locked = Vec::new();
+        let mut skipped = Vec::new();
+        let mut steps = Vec::new();
+        for v in victories {
+            if reachable(&locked, &v.loser, &v.winner) {
+                steps.push(CountStep::Skipped(v.clone()));
+                skipped.push(v);
+            } else {
+                steps.push(CountStep::Locked(v.clone()));
+                locked.push(v);
+            }
+        }
+
+        // Candidates beaten, directly or not, through locked victories
+        let scores: HashMap<String, f64> = ch
+            .iter()
+            .map(|c1| {
+                let beaten = ch
+                    .iter()
+                    .filter(|c2| c1 != *c2 && reachable(&locked, c1, c2))
+                    .count();
+                (c1.to_string(), beaten as f64)
+            })
+            .collect();
+
+        steps.push(CountStep::Scores {
+            label: "Candidates beaten through locked victories".to_string(),
+            scores: scores.clone(),
+        });
+
+        ElectionResult::from_scores(Self::LONG_NAME, scores, self.0.get_count())
+            .with_explanation(steps)
+            .break_ties(self.0.get_parameters().get_tie_break(), &[])
+            .with_details(ResultDetails::RankedPairs { locked, skipped })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranked_pairs() {
+        let mut r = RankedPairs::new(&["A", "B", "C"]);
+
+        // A > B > C > A, C > A is the weakest victory
+        for (n, v) in [(7, "ABC"), (5, "BCA"), (4, "CAB")] {
+            for _ in 0..n {
+                r.vote(SingleBallot::Ranked(
+                    v.chars().map(|c| c.to_string()).collect(),
+                ))
+                .unwrap();
+            }
+        }
+
+        let result = r.result();
+        assert_eq!(
+            &vec![
+                vec!["A".to_string()],
+                vec!["B".to_string()],
+                vec!["C".to_string()]
+            ],
+            result.get_ranking()
+        );
+
+        match result.get_details() {
+            Some(ResultDetails::RankedPairs { locked, skipped }) => {
+                assert_eq!(
+                    vec![("B", "C", 8), ("A", "B", 6)],
+                    locked
+                        .iter()
+                        .map(|v| (v.winner.as_str(), v.loser.as_str(), v.margin()))
+                        .collect::<Vec<_>>()
+                );
+                assert_eq!(1, skipped.len());
+                assert_eq!("C", skipped[0].winner);
+            }
+            _ => panic!("missing Ranked Pairs details"),
+        }
+    }
+}
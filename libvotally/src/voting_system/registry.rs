@@ -0,0 +1,225 @@
+use std::marker::PhantomData;
+use std::sync::{Arc, LazyLock, RwLock, RwLockReadGuard};
+
+use crate::voting_system::definition::{InvalidBallot, RawBallots, WeightedBallot};
+use crate::voting_system::{
+    Approval, BlackMethod, BordaCount, Copeland, ElectionResult, InstantRunoff, MajorityJudgment,
+    MinimalVotingSystemInfo, Minimax, Plurality, RankedPairs, Schulze, ScoreVoting, SingleBallot,
+    SingleTransferableVote, StarVoting, VotingParameters, VotingSystem,
+};
+
+/// Object-safe view of an election held with any voting system
+pub trait Election: Send {
+    /// Cast ballot
+    fn vote(&mut self, ballot: SingleBallot) -> Result<(), InvalidBallot>;
+
+    /// Cast ballot carrying several votes
+    fn vote_weighted(&mut self, ballot: WeightedBallot) -> Result<(), InvalidBallot>;
+
+    /// Calculate the election's result
+    fn result(&self) -> ElectionResult;
+
+    /// Get minimal information about this election
+    fn get_minimal_info(&self) -> MinimalVotingSystemInfo;
+
+    /// Get every accepted ballot
+    fn get_raw_ballots(&self) -> &RawBallots;
+}
+
+impl<V: VotingSystem + Send> Election for V {
+    fn vote(&mut self, ballot: SingleBallot) -> Result<(), InvalidBallot> {
+        VotingSystem::vote(self, ballot)
+    }
+
+    fn vote_weighted(&mut self, ballot: WeightedBallot) -> Result<(), InvalidBallot> {
+        VotingSystem::vote_weighted(self, ballot)
+    }
+
+    fn result(&self) -> ElectionResult {
+        VotingSystem::result(self)
+    }
+
+    fn get_minimal_info(&self) -> MinimalVotingSystemInfo {
+        VotingSystem::get_minimal_info(self)
+    }
+
+    fn get_raw_ballots(&self) -> &RawBallots {
+        VotingSystem::get_raw_ballots(self)
+    }
+}
+
+/// Create elections held with one voting system
+pub trait VotingSystemFactory: Send + Sync {
+    /// Short name of the voting system, used to choose it
+    fn name(&self) -> &'static str;
+
+    /// Full name of the voting system
+    fn long_name(&self) -> &'static str;
+
+    /// Check if the voting system fills several seats
+    fn multi_winner(&self) -> bool {
+        false
+    }
+
+    /// Create a new election
+    fn create(&self, choices: &[&str], parameters: VotingParameters) -> Box<dyn Election>;
+}
+
+/// Factory of a type implementing VotingSystem
+struct Factory<V>(PhantomData<fn() -> V>);
+
+impl<V: VotingSystem + Send + 'static> VotingSystemFactory for Factory<V> {
+    fn name(&self) -> &'static str {
+        V::NAME
+    }
+
+    fn long_name(&self) -> &'static str {
+        V::LONG_NAME
+    }
+
+    fn multi_winner(&self) -> bool {
+        V::MULTI_WINNER
+    }
+
+    fn create(&self, choices: &[&str], parameters: VotingParameters) -> Box<dyn Election> {
+        Box::new(V::with_parameters(choices, parameters))
+    }
+}
+
+/// Known voting systems, in the order they were registered
+pub struct Registry(Vec<Arc<dyn VotingSystemFactory>>);
+
+impl Registry {
+    /// Create a registry with every voting system of this library
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Box::new(Factory::<Plurality>(PhantomData)));
+        registry.register(Box::new(Factory::<Approval>(PhantomData)));
+        registry.register(Box::new(Factory::<BordaCount>(PhantomData)));
+        registry.register(Box::new(Factory::<BlackMethod>(PhantomData)));
+        registry.register(Box::new(Factory::<InstantRunoff>(PhantomData)));
+        registry.register(Box::new(Factory::<Schulze>(PhantomData)));
+        registry.register(Box::new(Factory::<RankedPairs>(PhantomData)));
+        registry.register(Box::new(Factory::<Copeland>(PhantomData)));
+        registry.register(Box::new(Factory::<Minimax>(PhantomData)));
+        registry.register(Box::new(Factory::<SingleTransferableVote>(PhantomData)));
+        registry.register(Box::new(Factory::<ScoreVoting>(PhantomData)));
+        registry.register(Box::new(Factory::<StarVoting>(PhantomData)));
+        registry.register(Box::new(Factory::<MajorityJudgment>(PhantomData)));
+        registry
+    }
+
+    /// Create a registry without any voting system
+    pub fn empty() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Add a voting system, replacing any voting system with the same name
+    pub fn register(&mut self, factory: Box<dyn VotingSystemFactory>) {
+        self.0.retain(|f| f.name() != factory.name());
+        self.0.push(Arc::from(factory));
+    }
+
+    /// Find the voting system which is associated to name,
+    /// which can still be used once the registry is released
+    pub fn find(&self, name: &str) -> Option<Arc<dyn VotingSystemFactory>> {
+        self.0.iter().find(|f| f.name() == name).cloned()
+    }
+
+    /// Get every voting system
+    pub fn factories(&self) -> impl Iterator<Item = &dyn VotingSystemFactory> {
+        self.0.iter().map(|f| f.as_ref())
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static REGISTRY: LazyLock<RwLock<Registry>> = LazyLock::new(|| RwLock::new(Registry::new()));
+
+/// Get the global registry of voting systems
+pub fn registry() -> RwLockReadGuard<'static, Registry> {
+    REGISTRY.read().unwrap()
+}
+
+/// Add a voting system to the global registry
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::voting_system::{
+///     Election, VotingParameters, VotingSystemFactory, Plurality, VotingSystem,
+///     find_voting_system, register_factory,
+/// };
+///
+/// struct FirstPastThePost;
+///
+/// impl VotingSystemFactory for FirstPastThePost {
+///     fn name(&self) -> &'static str {
+///         "fptp"
+///     }
+///
+///     fn long_name(&self) -> &'static str {
+///         "First-past-the-post"
+///     }
+///
+///     fn create(&self, choices: &[&str], parameters: VotingParameters) -> Box<dyn Election> {
+///         Box::new(Plurality::with_parameters(choices, parameters))
+///     }
+/// }
+///
+/// register_factory(Box::new(FirstPastThePost));
+///
+/// assert!(find_voting_system("fptp", &["A", "B"], &VotingParameters::default()).is_ok());
+/// ```
+pub fn register_factory(factory: Box<dyn VotingSystemFactory>) {
+    REGISTRY.write().unwrap().register(factory);
+}
+
+/// Add a type implementing VotingSystem to the global registry
+pub fn register_voting_system<V: VotingSystem + Send + 'static>() {
+    register_factory(Box::new(Factory::<V>(PhantomData)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_registry() {
+        assert_eq!(0, Registry::empty().factories().count());
+
+        let mut registry = Registry::new();
+        assert_eq!(13, registry.factories().count());
+        assert_eq!(
+            Some("Borda count"),
+            registry.find("borda").map(|f| f.long_name())
+        );
+
+        // Registering a known name replaces the voting system
+        registry.register(Box::new(Factory::<Plurality>(PhantomData)));
+        assert_eq!(13, registry.factories().count());
+        assert_eq!(
+            Some("plurality"),
+            registry.factories().last().map(|f| f.name())
+        );
+        assert!(registry.find("unknown").is_none());
+    }
+
+    #[test]
+    fn factory_outlives_the_registry() {
+        let mut registry = Registry::new();
+        let plurality = registry.find("plurality").unwrap();
+
+        // The found factory doesn't borrow the registry, which can change or go away
+        registry.register(Box::new(Factory::<Plurality>(PhantomData)));
+        drop(registry);
+
+        let mut vote = plurality.create(&["A", "B"], VotingParameters::default());
+        vote.vote(SingleBallot::Uninominal("A".to_string()))
+            .unwrap();
+        assert_eq!(vec!["A".to_string()], vote.result().get_winners());
+    }
+}
@@ -0,0 +1,456 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::voting_system::{CountStep, PairwiseMatrix, TieBreak};
+
+/// Result of an election
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::voting_system::{Plurality, VotingSystem, SingleBallot};
+///
+/// let mut p = Plurality::new(&["A", "B", "C"]);
+///
+/// p.vote(SingleBallot::Uninominal("A".to_string())).unwrap();
+/// p.vote(SingleBallot::Uninominal("B".to_string())).unwrap();
+/// p.vote(SingleBallot::Uninominal("A".to_string())).unwrap();
+///
+/// let result = p.result();
+/// assert_eq!(vec!["A".to_string()], result.get_winners());
+/// assert_eq!(Some(1.), result.get_margin());
+/// assert_eq!(3, result.get_count());
+/// assert!(!result.is_tie());
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ElectionResult {
+    /// The name of the voting system used
+    voting_system: String,
+    /// Candidates from best to worst, candidates sharing a rank are tied
+    ranking: Vec<Vec<String>>,
+    /// Score of each candidate under the voting system used
+    scores: HashMap<String, f64>,
+    /// Total number of ballots
+    count: usize,
+    /// Number of seats filled
+    seats: usize,
+    /// Whether a tie occurred
+    tie: bool,
+    /// Policy used to break ties
+    tie_break: TieBreak,
+    /// Details specific to the voting system used
+    details: Option<ResultDetails>,
+    /// Steps of the count explaining the result
+    explanation: Vec<CountStep>,
+}
+
+impl ElectionResult {
+    /// Create a new result from a ranking
+    pub(crate) fn new(
+        voting_system: &str,
+        ranking: Vec<Vec<String>>,
+        scores: HashMap<String, f64>,
+        count: usize,
+    ) -> Self {
+        let tie = ranking.iter().any(|rank| rank.len() > 1);
+
+        Self {
+            voting_system: voting_system.to_owned(),
+            ranking,
+            scores,
+            count,
+            seats: 1,
+            tie,
+            tie_break: TieBreak::DeclareTie,
+            details: None,
+            explanation: Vec::new(),
+        }
+    }
+
+    /// Create a new result where the best candidates have the highest scores
+    pub(crate) fn from_scores(
+        voting_system: &str,
+        scores: HashMap<String, f64>,
+        count: usize,
+    ) -> Self {
+        let ranking = rank_by_scores(&scores);
+        Self::new(voting_system, ranking, scores, count)
+    }
+
+    /// Set the number of seats filled
+    pub(crate) fn with_seats(mut self, seats: usize) -> Self {
+        self.seats = seats;
+        self
+    }
+
+    /// Add details specific to the voting system used
+    pub(crate) fn with_details(mut self, details: ResultDetails) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    /// Add several steps to the explanation of the count
+    pub(crate) fn with_explanation(mut self, steps: Vec<CountStep>) -> Self {
+        self.explanation.extend(steps);
+        self
+    }
+
+    /// Record that a tie occurred while counting, even if it doesn't appear in the ranking
+    pub(crate) fn with_tie(mut self, tie: bool) -> Self {
+        self.tie |= tie;
+        self
+    }
+
+    /// Break every tie of the ranking with a tie-breaking policy
+    /// `earlier_rounds` contains the scores of previous rounds, from the first one to the last one.
+    pub(crate) fn break_ties(
+        mut self,
+        tie_break: &TieBreak,
+        earlier_rounds: &[HashMap<String, f64>],
+    ) -> Self {
+        let mut steps = Vec::new();
+        self.ranking = self
+            .ranking
+            .into_iter()
+            .flat_map(|rank| {
+                if rank.len() > 1 {
+                    let broken = tie_break.break_tie(&rank, earlier_rounds);
+                    steps.push(CountStep::TieBroken {
+                        tied: rank,
+                        ranking: broken.clone(),
+                    });
+                    broken
+                } else {
+                    vec![rank]
+                }
+            })
+            .collect();
+        self.explanation.extend(steps);
+        self.tie_break = tie_break.clone();
+        self
+    }
+
+    /// Get the name of the voting system used
+    pub fn get_voting_system(&self) -> &str {
+        &self.voting_system[..]
+    }
+
+    /// Get the ranking from best to worst
+    pub fn get_ranking(&self) -> &Vec<Vec<String>> {
+        &self.ranking
+    }
+
+    /// Get the score of every candidate
+    pub fn get_scores(&self) -> &HashMap<String, f64> {
+        &self.scores
+    }
+
+    /// Get the score of one candidate
+    pub fn get_score(&self, candidate: &str) -> Option<f64> {
+        self.scores.get(candidate).copied()
+    }
+
+    /// Get the total number of ballots
+    pub fn get_count(&self) -> usize {
+        self.count
+    }
+
+    /// Return true if at least two candidates were tied, even if the tie was broken
+    pub fn is_tie(&self) -> bool {
+        self.tie
+    }
+
+    /// Get the policy used to break ties
+    pub fn get_tie_break(&self) -> &TieBreak {
+        &self.tie_break
+    }
+
+    /// Get details specific to the voting system used
+    pub fn get_details(&self) -> Option<&ResultDetails> {
+        self.details.as_ref()
+    }
+
+    /// Get the steps of the count explaining the result
+    pub fn get_explanation(&self) -> &[CountStep] {
+        &self.explanation
+    }
+
+    /// Get the best candidates, several if they are tied
+    pub fn get_winners(&self) -> Vec<String> {
+        self.ranking.first().cloned().unwrap_or_default()
+    }
+
+    /// Get the number of seats filled
+    pub fn get_seats(&self) -> usize {
+        self.seats
+    }
+
+    /// Get the elected candidates in order of election, as many as seats,
+    /// fewer if candidates are tied for the last seats
+    pub fn get_elected(&self) -> Vec<String> {
+        let mut elected = Vec::new();
+        for rank in &self.ranking {
+            if elected.len() + rank.len() > self.seats {
+                break;
+            }
+            elected.extend(rank.iter().cloned());
+        }
+        elected
+    }
+
+    /// Get the candidates ranked just after the winners
+    pub fn get_runner_up(&self) -> Vec<String> {
+        self.ranking.get(1).cloned().unwrap_or_default()
+    }
+
+    /// Get the score difference between the winners and the runner-up
+    pub fn get_margin(&self) -> Option<f64> {
+        let winner = self.get_score(self.ranking.first()?.first()?)?;
+        let runner_up = self.get_score(self.ranking.get(1)?.first()?)?;
+        Some(winner - runner_up)
+    }
+}
+
+/// Victory of a candidate over another one in a one-to-one comparison
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PairwiseVictory {
+    /// The preferred candidate
+    pub winner: String,
+    /// The defeated candidate
+    pub loser: String,
+    /// Number of ballots preferring the winner to the loser
+    pub winning_votes: i64,
+    /// Number of ballots preferring the loser to the winner
+    pub losing_votes: i64,
+}
+
+impl PairwiseVictory {
+    /// Get the difference between winning and losing votes
+    pub fn margin(&self) -> i64 {
+        self.winning_votes - self.losing_votes
+    }
+}
+
+impl fmt::Display for PairwiseVictory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} > {} ({} to {})",
+            self.winner, self.loser, self.winning_votes, self.losing_votes
+        )
+    }
+}
+
+/// Details specific to a voting system
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ResultDetails {
+    /// Pairwise victories locked in or skipped because they would create a cycle
+    RankedPairs {
+        locked: Vec<PairwiseVictory>,
+        skipped: Vec<PairwiseVictory>,
+    },
+    /// Quota of votes needed to be elected
+    Quota(f64),
+    /// Final one-to-one comparison between the two best candidates
+    Runoff(PairwiseVictory),
+    /// Number of ballots preferring each candidate to each other one
+    Pairwise(PairwiseMatrix),
+    /// Number of times each grade, from the best to the worst, was given to each candidate
+    Grades {
+        grades: Vec<String>,
+        distributions: HashMap<String, Vec<u64>>,
+    },
+}
+
+impl fmt::Display for ResultDetails {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResultDetails::RankedPairs { locked, skipped } => {
+                write!(f, "Locked pairs:")?;
+                for v in locked {
+                    write!(f, "\n  {}", v)?;
+                }
+                write!(f, "\nSkipped pairs:")?;
+                for v in skipped {
+                    write!(f, "\n  {}", v)?;
+                }
+                Ok(())
+            }
+            ResultDetails::Quota(quota) => write!(f, "Quota: {}", format_score(*quota)),
+            ResultDetails::Runoff(victory) => write!(f, "Runoff: {}", victory),
+            ResultDetails::Pairwise(matrix) => write!(f, "Pairwise preferences:\n{}", matrix),
+            ResultDetails::Grades {
+                grades,
+                distributions,
+            } => {
+                write!(f, "Grades:")?;
+                let mut candidates: Vec<&String> = distributions.keys().collect();
+                candidates.sort();
+                for c in candidates {
+                    let distribution = &distributions[c];
+                    write!(f, "\n  {}", c)?;
+                    if let Some(m) = lower_median(distribution) {
+                        write!(f, " (median {})", grades[m])?;
+                    }
+                    let counts: Vec<String> = grades
+                        .iter()
+                        .zip(distribution)
+                        .filter(|(_, n)| **n > 0)
+                        .map(|(g, n)| format!("{} {}", g, n))
+                        .collect();
+                    write!(f, ": {}", counts.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Group candidates by decreasing scores
+/// Tied candidates are sorted by name
+pub(crate) fn rank_by_scores(scores: &HashMap<String, f64>) -> Vec<Vec<String>> {
+    let mut sorted: Vec<(&String, &f64)> = scores.iter().collect();
+    sorted.sort_by(|a, b| {
+        b.1.partial_cmp(a.1)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.0.cmp(b.0))
+    });
+
+    let mut ranking: Vec<Vec<String>> = Vec::new();
+    let mut last_score = None;
+    for (c, s) in sorted {
+        match (ranking.last_mut(), last_score) {
+            (Some(rank), Some(last)) if last == *s => rank.push(c.to_owned()),
+            _ => ranking.push(vec![c.to_owned()]),
+        }
+        last_score = Some(*s);
+    }
+
+    ranking
+}
+
+/// Get the lower median grade of a distribution ordered from the best grade to the worst
+pub(crate) fn lower_median(distribution: &[u64]) -> Option<usize> {
+    let middle = distribution.iter().sum::<u64>() / 2;
+    let mut count = 0;
+    distribution.iter().position(|n| {
+        count += n;
+        count > middle
+    })
+}
+
+/// Move a candidate alone at the top of a ranking
+pub(crate) fn move_first(ranking: &mut Vec<Vec<String>>, candidate: &str) {
+    ranking
+        .iter_mut()
+        .for_each(|rank| rank.retain(|r| r != candidate));
+    ranking.retain(|rank| !rank.is_empty());
+    ranking.insert(0, vec![candidate.to_owned()]);
+}
+
+/// Write a score without useless decimals
+pub(crate) fn format_score(score: f64) -> String {
+    if score.fract() == 0. {
+        format!("{:.0}", score)
+    } else {
+        format!("{:.2}", score)
+    }
+}
+
+impl fmt::Display for ElectionResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let winners = self.get_winners();
+        if self.seats > 1 {
+            writeln!(f, "Elected: {}", self.get_elected().join(", "))?;
+        } else if winners.len() == 1 {
+            writeln!(f, "Winner: {}", winners[0])?;
+        } else {
+            writeln!(f, "Tie between {}", winners.join(", "))?;
+        }
+
+        write!(
+            f,
+            "Ranking with {} ({} ballots):",
+            self.voting_system, self.count
+        )?;
+        for (i, rank) in self.ranking.iter().enumerate() {
+            for c in rank {
+                write!(f, "\n{}. {}", i + 1, c)?;
+                if let Some(s) = self.get_score(c) {
+                    write!(f, " ({})", format_score(s))?;
+                }
+            }
+        }
+
+        if let Some(details) = &self.details {
+            write!(f, "\n{}", details)?;
+        }
+
+        if self.tie {
+            write!(f, "\nTie-breaking policy: {}", self.tie_break)?;
+        }
+
+        if !self.explanation.is_empty() {
+            write!(f, "\nExplanation:")?;
+            for (i, step) in self.explanation.iter().enumerate() {
+                write!(f, "\n{}. {}", i + 1, step)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranking_with_ties() {
+        let scores = HashMap::from([
+            ("A".to_string(), 2.),
+            ("B".to_string(), 5.),
+            ("C".to_string(), 2.),
+        ]);
+
+        let result = ElectionResult::from_scores("test", scores, 3);
+
+        assert_eq!(
+            &vec![
+                vec!["B".to_string()],
+                vec!["A".to_string(), "C".to_string()]
+            ],
+            result.get_ranking()
+        );
+        assert!(result.is_tie());
+        assert_eq!(Some(3.), result.get_margin());
+        assert_eq!(
+            vec!["A".to_string(), "C".to_string()],
+            result.get_runner_up()
+        );
+    }
+
+    #[test]
+    fn broken_ties() {
+        let scores = HashMap::from([
+            ("A".to_string(), 2.),
+            ("B".to_string(), 5.),
+            ("C".to_string(), 2.),
+        ]);
+
+        let tie_break = TieBreak::Priority(vec!["C".to_string()]);
+        let result = ElectionResult::from_scores("test", scores, 3).break_ties(&tie_break, &[]);
+
+        assert_eq!(
+            &vec![
+                vec!["B".to_string()],
+                vec!["C".to_string()],
+                vec!["A".to_string()]
+            ],
+            result.get_ranking()
+        );
+        assert!(result.is_tie());
+        assert_eq!(&tie_break, result.get_tie_break());
+    }
+}
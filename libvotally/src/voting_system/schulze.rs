@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use crate::voting_system::definition::*;
+use crate::voting_system::{CountStep, ElectionResult};
+
+/// # Schulze method
+/// The strength of a path between two candidates is its weakest pairwise victory.
+/// A candidate is ranked above another
+/// if its strongest path to it is stronger than the opposite one.
+/// See [Schulze_wikipedia].
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::voting_system::{Schulze, VotingSystem, SingleBallot};
+///
+/// let mut s = Schulze::new(&["A", "B", "C"]);
+///
+/// s.vote(SingleBallot::Ranked(vec![
+///     "A".to_string(),
+///     "B".to_string(),
+///     "C".to_string(),
+/// ])).unwrap();
+/// s.vote(SingleBallot::Ranked(vec![
+///     "C".to_string(),
+///     "A".to_string(),
+///     "B".to_string(),
+/// ])).unwrap();
+///
+/// assert_eq!(vec!["A".to_string()], s.result().get_winners());
+/// ```
+///
+/// [Schulze_wikipedia]: https://en.wikipedia.org/wiki/Schulze_method
+pub struct Schulze(VotingSystemInfo<BattleBallots>);
+
+impl VotingSystem for Schulze {
+    type B = BattleBallots;
+
+    const NAME: &str = "schulze";
+    const LONG_NAME: &str = "Schulze method";
+
+    fn with_parameters(choices: &[&str], parameters: VotingParameters) -> Self {
+        Self(VotingSystemInfo::new(
+            Self::LONG_NAME,
+            BallotForm::Ranked,
+            choices,
+            parameters,
+        ))
+    }
+
+    fn get_info(&self) -> &VotingSystemInfo<Self::B> {
+        &self.0
+    }
+
+    fn get_mut_info(&mut self) -> &mut VotingSystemInfo<Self::B> {
+        &mut self.0
+    }
+
+    fn result(&self) -> ElectionResult {
+        let BattleBallots(b) = self.0.get_ballot_box();
+        let mut ch: Vec<&String> = self.0.get_choices().collect();
+        ch.sort();
+        let n = ch.len();
+
+        // Strength of the strongest path between each pair of candidates
+        let mut p = vec![vec![0; n]; n];
+        for i in 0..n {
+            for j in 0..n {
+                let d_ij = b[&(ch[i].to_owned(), ch[j].to_owned())];
+                let d_ji = b[&(ch[j].to_owned(), ch[i].to_owned())];
+                if i != j && d_ij > d_ji {
+                    p[i][j] = d_ij;
+                }
+            }
+        }
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    if i != k && j != k && i != j {
+                        p[i][j] = p[i][j].max(p[i][k].min(p[k][j]));
+                    }
+                }
+            }
+        }
+
+        let mut steps = Vec::new();
+        for i in 0..n {
+            for j in 0..n {
+                if p[i][j] > p[j][i] {
+                    steps.push(CountStep::StrongestPath {
+                        winner: ch[i].to_owned(),
+                        loser: ch[j].to_owned(),
+                        strength: p[i][j],
+                        opposite: p[j][i],
+                    });
+                }
+            }
+        }
+
+        // Number of candidates beaten through the strongest paths
+        let scores: HashMap<String, f64> = (0..n)
+            .map(|i| {
+                let wins = (0..n).filter(|&j| p[i][j] > p[j][i]).count();
+                (ch[i].to_owned(), wins as f64)
+            })
+            .collect();
+
+        steps.push(CountStep::Scores {
+            label: "Candidates beaten through the strongest paths".to_string(),
+            scores: scores.clone(),
+        });
+
+        ElectionResult::from_scores(Self::LONG_NAME, scores, self.0.get_count())
+            .with_explanation(steps)
+            .break_ties(self.0.get_parameters().get_tie_break(), &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schulze_method() {
+        let mut s = Schulze::new(&["A", "B", "C", "D", "E"]);
+
+        // Example from Wikipedia
+        for (n, v) in [
+            (5, "ACBED"),
+            (5, "ADECB"),
+            (8, "BEDAC"),
+            (3, "CABED"),
+            (7, "CAEBD"),
+            (2, "CBADE"),
+            (7, "DCEBA"),
+            (8, "EBADC"),
+        ] {
+            for _ in 0..n {
+                s.vote(SingleBallot::Ranked(
+                    v.chars().map(|c| c.to_string()).collect(),
+                ))
+                .unwrap();
+            }
+        }
+
+        assert_eq!(
+            &vec![
+                vec!["E".to_string()],
+                vec!["A".to_string()],
+                vec!["C".to_string()],
+                vec!["B".to_string()],
+                vec!["D".to_string()]
+            ],
+            s.result().get_ranking()
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use crate::voting_system::definition::*;
+use crate::voting_system::{CountStep, ElectionResult};
+
+/// # Score voting
+/// Each voter gives a score to every candidate within the range of the election.
+/// The candidate with the highest average score wins.
+/// See [Score_wikipedia].
+///
+/// Here an exemple :
+/// ```rust
+/// use std::collections::HashMap;
+/// use libvotally::voting_system::{ScoreVoting, VotingSystem, SingleBallot};
+///
+/// let mut s = ScoreVoting::new(&["A", "B", "C"]);
+///
+/// s.vote(SingleBallot::Scored(HashMap::from([
+///     ("A".to_string(), 5),
+///     ("B".to_string(), 3),
+///     ("C".to_string(), 0),
+/// ]))).unwrap();
+/// s.vote(SingleBallot::Scored(HashMap::from([
+///     ("A".to_string(), 2),
+///     ("B".to_string(), 3),
+///     ("C".to_string(), 5),
+/// ]))).unwrap();
+///
+/// let result = s.result();
+/// assert_eq!(vec!["A".to_string()], result.get_winners());
+/// assert_eq!(Some(3.5), result.get_score("A"));
+/// ```
+///
+/// [Score_wikipedia]: https://en.wikipedia.org/wiki/Score_voting
+pub struct ScoreVoting(VotingSystemInfo<PointBallots>);
+
+impl VotingSystem for ScoreVoting {
+    type B = PointBallots;
+
+    const NAME: &str = "score";
+    const LONG_NAME: &str = "Score voting";
+
+    fn with_parameters(choices: &[&str], parameters: VotingParameters) -> Self {
+        Self(VotingSystemInfo::new(
+            Self::LONG_NAME,
+            BallotForm::Scored,
+            choices,
+            parameters,
+        ))
+    }
+
+    fn get_info(&self) -> &VotingSystemInfo<PointBallots> {
+        &self.0
+    }
+
+    fn get_mut_info(&mut self) -> &mut VotingSystemInfo<PointBallots> {
+        &mut self.0
+    }
+
+    fn result(&self) -> ElectionResult {
+        let PointBallots(c) = self.0.get_ballot_box();
+        let count = self.0.get_total_weight().max(1) as f64;
+        let scores: HashMap<String, f64> = c
+            .iter()
+            .map(|(k, v)| (k.to_owned(), *v as f64 / count))
+            .collect();
+        let average = CountStep::Scores {
+            label: "Average score".to_string(),
+            scores: scores.clone(),
+        };
+
+        ElectionResult::from_scores(Self::LONG_NAME, scores, self.0.get_count())
+            .with_explanation(vec![average])
+            .break_ties(self.0.get_parameters().get_tie_break(), &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn score_voting() {
+        let parameters =
+            VotingParameters::default().with_score_range(ScoreRange::new(0, 10).unwrap());
+        let mut s = ScoreVoting::with_parameters(&["A", "B", "C"], parameters);
+
+        for (a, b, c) in [(10, 0, 5), (0, 10, 6), (3, 7, 7)] {
+            s.vote(SingleBallot::Scored(HashMap::from([
+                ("A".to_string(), a),
+                ("B".to_string(), b),
+                ("C".to_string(), c),
+            ])))
+            .unwrap();
+        }
+
+        let result = s.result();
+        assert_eq!(
+            &vec![
+                vec!["C".to_string()],
+                vec!["B".to_string()],
+                vec!["A".to_string()]
+            ],
+            result.get_ranking()
+        );
+        assert!(
+            s.vote(SingleBallot::Scored(HashMap::from([
+                ("A".to_string(), 11),
+                ("B".to_string(), 0),
+                ("C".to_string(), 0),
+            ])))
+            .is_err()
+        );
+    }
+}
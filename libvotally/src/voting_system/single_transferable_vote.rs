@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+
+use crate::voting_system::definition::*;
+use crate::voting_system::explanation::tie_step;
+use crate::voting_system::instant_runoff::can_eliminate_together;
+use crate::voting_system::result::rank_by_scores;
+use crate::voting_system::{CountStep, ElectionResult, ResultDetails};
+
+/// # Single transferable vote
+/// Elect several candidates with the Droop quota.
+/// Each round, ballots count for their most preferred remaining candidate.
+/// Candidates reaching the quota are elected
+/// and the surplus of their votes is transferred to the next preferences
+/// by reducing the value of all their ballots (weighted inclusive Gregory method).
+/// Otherwise the candidate with the fewest votes is eliminated
+/// and its ballots are transferred at their current value.
+/// The value of a ballot ranking several remaining candidates equally is split between them.
+/// See [STV_wikipedia].
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::voting_system::{
+///     SingleTransferableVote, SingleBallot, VotingParameters, VotingSystem
+/// };
+///
+/// let mut stv = SingleTransferableVote::with_parameters(
+///     &["A", "B", "C"],
+///     VotingParameters::default().with_seats(2),
+/// );
+///
+/// for v in [
+///     ["A", "B", "C"],
+///     ["A", "B", "C"],
+///     ["A", "C", "B"],
+///     ["B", "A", "C"],
+///     ["C", "B", "A"],
+/// ] {
+///     stv.vote(SingleBallot::Ranked(v.iter().map(|s| s.to_string()).collect()))
+///         .unwrap();
+/// }
+///
+/// assert_eq!(
+///     vec!["A".to_string(), "B".to_string()],
+///     stv.result().get_elected()
+/// );
+/// ```
+///
+/// [STV_wikipedia]: https://en.wikipedia.org/wiki/Single_transferable_vote
+pub struct SingleTransferableVote(VotingSystemInfo<RankedBallots>);
+
+impl VotingSystem for SingleTransferableVote {
+    type B = RankedBallots;
+
+    const NAME: &str = "stv";
+    const LONG_NAME: &str = "Single transferable vote";
+    const MULTI_WINNER: bool = true;
+
+    fn with_parameters(choices: &[&str], parameters: VotingParameters) -> Self {
+        Self(VotingSystemInfo::new(
+            Self::LONG_NAME,
+            BallotForm::Ranked,
+            choices,
+            parameters,
+        ))
+    }
+
+    fn get_info(&self) -> &VotingSystemInfo<Self::B> {
+        &self.0
+    }
+
+    fn get_mut_info(&mut self) -> &mut VotingSystemInfo<Self::B> {
+        &mut self.0
+    }
+
+    fn result(&self) -> ElectionResult {
+        let RankedBallots {
+            choices,
+            ballots,
+            weights,
+        } = self.0.get_ballot_box();
+        let tie_break = self.0.get_parameters().get_tie_break();
+        let seats = self.0.get_parameters().get_seats();
+
+        let quota = (self.0.get_total_weight() as usize / (seats + 1) + 1) as f64;
+        let mut weights: Vec<f64> = weights.iter().map(|w| *w as f64).collect();
+
+        let mut hopeful: Vec<String> = choices.clone();
+        let mut elected: Vec<String> = Vec::new();
+        let mut eliminated: Vec<Vec<String>> = Vec::new();
+        let mut rounds: Vec<HashMap<String, f64>> = Vec::new();
+        let mut scores: HashMap<String, f64> = HashMap::new();
+        let mut tie = false;
+        let mut unresolved = false;
+        let mut steps = Vec::new();
+
+        while elected.len() < seats && !hopeful.is_empty() {
+            // Count the value of ballots for their most preferred remaining candidate
+            let mut count: HashMap<String, f64> =
+                hopeful.iter().map(|c| (c.to_owned(), 0.)).collect();
+            // Equally ranked candidates share the value of the ballot
+            for (b, w) in ballots.iter().zip(&weights) {
+                let preferred = RankedBallots::preferred(b, &hopeful);
+                for c in &preferred {
+                    count
+                        .entry(c.to_string())
+                        .and_modify(|v| *v += w / preferred.len() as f64);
+                }
+            }
+            count.iter().for_each(|(c, v)| {
+                scores.insert(c.to_owned(), *v);
+            });
+            steps.push(CountStep::Scores {
+                label: format!("Round {}", rounds.len() + 1),
+                scores: count.clone(),
+            });
+
+            // Every remaining candidate gets a seat
+            if hopeful.len() + elected.len() <= seats {
+                let ranking = rank_by_scores(&count);
+                tie |= ranking.iter().any(|rank| rank.len() > 1);
+                let remaining: Vec<String> = ranking
+                    .into_iter()
+                    .flat_map(|rank| tie_break.break_tie(&rank, &rounds))
+                    .flatten()
+                    .collect();
+                steps.push(CountStep::Elected(remaining.clone()));
+                elected.extend(remaining);
+                hopeful.clear();
+                break;
+            }
+
+            let reached: HashMap<String, f64> = count
+                .iter()
+                .filter(|(_, v)| **v >= quota)
+                .map(|(c, v)| (c.to_owned(), *v))
+                .collect();
+
+            if !reached.is_empty() {
+                // Elect candidates reaching the quota and transfer their surplus
+                for (b, w) in ballots.iter().zip(weights.iter_mut()) {
+                    let preferred = RankedBallots::preferred(b, &hopeful);
+                    if preferred.iter().any(|c| reached.contains_key(*c)) {
+                        let kept: f64 = preferred
+                            .iter()
+                            .map(|c| reached.get(*c).map_or(1., |v| (v - quota) / v))
+                            .sum();
+                        *w *= kept / preferred.len() as f64;
+                    }
+                }
+
+                let ranking = rank_by_scores(&reached);
+                tie |= ranking.iter().any(|rank| rank.len() > 1);
+                let newly_elected: Vec<String> = ranking
+                    .into_iter()
+                    .flat_map(|rank| tie_break.break_tie(&rank, &rounds))
+                    .flatten()
+                    .collect();
+                steps.push(CountStep::Elected(newly_elected.clone()));
+                for c in &newly_elected {
+                    let v = reached[c];
+                    if v > quota {
+                        steps.push(CountStep::SurplusTransferred {
+                            candidate: c.to_owned(),
+                            surplus: v - quota,
+                            ratio: (v - quota) / v,
+                        });
+                    }
+                }
+                elected.extend(newly_elected);
+                hopeful.retain(|c| !reached.contains_key(c));
+            } else {
+                // Eliminate the candidates with the fewest votes
+                let fewest = count.values().cloned().fold(f64::INFINITY, f64::min);
+                let lowest: Vec<String> = hopeful
+                    .iter()
+                    .filter(|c| count[*c] == fewest)
+                    .cloned()
+                    .collect();
+                let lowest = if lowest.len() > 1 {
+                    tie = true;
+                    let worst = tie_break.worst(&lowest, &rounds);
+                    steps.push(tie_step(lowest, &worst));
+                    worst
+                } else {
+                    lowest
+                };
+
+                if hopeful.len() - lowest.len() + elected.len() < seats {
+                    // The remaining seats can't be separated between tied candidates
+                    rounds.push(count);
+                    break;
+                }
+                if lowest.len() > 1 && !can_eliminate_together(&count, &lowest) {
+                    // Who gets the remaining seats depends on which tied candidate is eliminated
+                    unresolved = true;
+                    rounds.push(count);
+                    break;
+                }
+                hopeful.retain(|c| !lowest.contains(c));
+                steps.push(CountStep::Eliminated(lowest.clone()));
+                eliminated.push(lowest);
+            }
+
+            rounds.push(count);
+        }
+
+        // Elected candidates first, then remaining ones and eliminated ones
+        let mut ranking: Vec<Vec<String>> = elected.into_iter().map(|c| vec![c]).collect();
+        if unresolved {
+            // Which remaining candidates get the last seats depends on the tie
+            hopeful.sort();
+            ranking.push(hopeful);
+        } else {
+            let remaining: HashMap<String, f64> =
+                hopeful.iter().map(|c| (c.to_owned(), scores[c])).collect();
+            ranking.extend(rank_by_scores(&remaining));
+        }
+        ranking.extend(eliminated.into_iter().rev());
+
+        ElectionResult::new(Self::LONG_NAME, ranking, scores, self.0.get_count())
+            .with_explanation(steps)
+            .with_tie(tie)
+            .with_seats(seats)
+            .break_ties(tie_break, &rounds)
+            .with_details(ResultDetails::Quota(quota))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_transferable_vote() {
+        let mut stv = SingleTransferableVote::with_parameters(
+            &[
+                "Orange",
+                "Pear",
+                "Strawberry",
+                "Cake",
+                "Chocolate",
+                "Hamburger",
+            ],
+            VotingParameters::default().with_seats(3),
+        );
+
+        // Example from Wikipedia
+        for (n, v) in [
+            (4, vec!["Orange", "Pear"]),
+            (2, vec!["Pear", "Strawberry", "Cake"]),
+            (8, vec!["Chocolate", "Strawberry", "Orange"]),
+            (4, vec!["Chocolate", "Cake", "Pear"]),
+            (1, vec!["Strawberry", "Orange"]),
+            (1, vec!["Hamburger", "Orange"]),
+        ] {
+            // Complete each ranking in a neutral order
+            let mut ranked: Vec<String> = v.iter().map(|s| s.to_string()).collect();
+            for c in [
+                "Hamburger",
+                "Cake",
+                "Strawberry",
+                "Pear",
+                "Orange",
+                "Chocolate",
+            ] {
+                if !v.contains(&c) {
+                    ranked.push(c.to_string());
+                }
+            }
+
+            for _ in 0..n {
+                stv.vote(SingleBallot::Ranked(ranked.clone())).unwrap();
+            }
+        }
+
+        assert!(
+            stv.get_minimal_info()
+                .to_string()
+                .contains("Number of seats: 3")
+        );
+
+        // Pear and Cake are tied and eliminated together
+        let result = stv.result();
+        assert_eq!(3, result.get_seats());
+        assert_eq!(
+            vec![
+                "Chocolate".to_string(),
+                "Strawberry".to_string(),
+                "Orange".to_string()
+            ],
+            result.get_elected()
+        );
+        assert!(result.is_tie());
+        assert!(matches!(
+            result.get_details(),
+            Some(ResultDetails::Quota(6.))
+        ));
+    }
+
+    #[test]
+    fn unresolved_tie_for_the_last_seat() {
+        let mut stv = SingleTransferableVote::new(&["A", "B", "C"]);
+        for (n, v) in [
+            (3, ["A", "B", "C"]),
+            (2, ["B", "A", "C"]),
+            (2, ["C", "B", "A"]),
+        ] {
+            for _ in 0..n {
+                stv.vote(SingleBallot::Ranked(
+                    v.iter().map(|s| s.to_string()).collect(),
+                ))
+                .unwrap();
+            }
+        }
+
+        // A wins if B is eliminated and B wins if C is, so nobody gets the seat
+        let result = stv.result();
+        assert!(result.is_tie());
+        assert!(result.get_elected().is_empty());
+        assert_eq!(
+            &vec![vec!["A".to_string(), "B".to_string(), "C".to_string()]],
+            result.get_ranking()
+        );
+    }
+}
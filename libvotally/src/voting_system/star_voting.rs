@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use crate::voting_system::definition::*;
+use crate::voting_system::result::rank_by_scores;
+use crate::voting_system::{CountStep, ElectionResult, PairwiseVictory, ResultDetails};
+
+/// # STAR voting
+/// Score Then Automatic Runoff.
+/// Each voter gives a score to every candidate.
+/// The two candidates with the highest total scores go to a runoff
+/// won by the one scored higher than the other on more ballots.
+/// If the runoff is tied, the candidate with the highest total score wins,
+/// then the tie-breaking policy of the election decides.
+/// See [STAR_wikipedia].
+///
+/// Here an exemple :
+/// ```rust
+/// use std::collections::HashMap;
+/// use libvotally::voting_system::{StarVoting, VotingSystem, SingleBallot};
+///
+/// let mut s = StarVoting::new(&["A", "B", "C"]);
+///
+/// for (a, b, c) in [(5, 4, 0), (5, 4, 0), (0, 1, 5)] {
+///     s.vote(SingleBallot::Scored(HashMap::from([
+///         ("A".to_string(), a),
+///         ("B".to_string(), b),
+///         ("C".to_string(), c),
+///     ]))).unwrap();
+/// }
+///
+/// // A and B go to the runoff, where A is preferred on two ballots out of three
+/// assert_eq!(vec!["A".to_string()], s.result().get_winners());
+/// ```
+///
+/// [STAR_wikipedia]: https://en.wikipedia.org/wiki/STAR_voting
+pub struct StarVoting(VotingSystemInfo<(PointBallots, BattleBallots)>);
+
+impl VotingSystem for StarVoting {
+    type B = (PointBallots, BattleBallots);
+
+    const NAME: &str = "star";
+    const LONG_NAME: &str = "STAR voting";
+
+    fn with_parameters(choices: &[&str], parameters: VotingParameters) -> Self {
+        Self(VotingSystemInfo::new(
+            Self::LONG_NAME,
+            BallotForm::Scored,
+            choices,
+            parameters,
+        ))
+    }
+
+    fn get_info(&self) -> &VotingSystemInfo<Self::B> {
+        &self.0
+    }
+
+    fn get_mut_info(&mut self) -> &mut VotingSystemInfo<Self::B> {
+        &mut self.0
+    }
+
+    fn result(&self) -> ElectionResult {
+        let (PointBallots(p), b) = self.0.get_ballot_box();
+        let tie_break = self.0.get_parameters().get_tie_break();
+
+        let scores: HashMap<String, f64> =
+            p.iter().map(|(k, v)| (k.to_owned(), *v as f64)).collect();
+        let mut tie = false;
+        let mut steps = vec![CountStep::Scores {
+            label: "Score round".to_string(),
+            scores: scores.clone(),
+        }];
+
+        // Score round
+        let mut ranking: Vec<Vec<String>> = rank_by_scores(&scores);
+
+        // The tie-breaking policy is only used when more than two candidates
+        // compete for the two places in the runoff
+        let contested = match ranking.as_slice() {
+            [first, ..] if first.len() > 2 => Some(0),
+            [first, second, ..] if first.len() == 1 && second.len() > 1 => Some(1),
+            _ => None,
+        };
+        if let Some(i) = contested {
+            tie = true;
+            let tied = ranking[i].clone();
+            let broken = tie_break.break_tie(&tied, &[]);
+            steps.push(CountStep::TieBroken {
+                tied,
+                ranking: broken.clone(),
+            });
+            ranking.splice(i..i + 1, broken);
+        }
+
+        // Automatic runoff, only if the two finalists are known
+        let finalists = match ranking.as_slice() {
+            [first, ..] if first.len() == 2 => Some((first[0].clone(), first[1].clone(), 1)),
+            [first, second, ..] if first.len() == 1 && second.len() == 1 => {
+                Some((first[0].clone(), second[0].clone(), 2))
+            }
+            _ => None,
+        };
+        // Candidates behind the finalists are still ranked by their scores
+        tie |= ranking
+            .iter()
+            .skip(finalists.as_ref().map_or(0, |f| f.2))
+            .any(|rank| rank.len() > 1);
+
+        let mut details = None;
+        if let Some((f1, f2, ranks)) = finalists {
+            let (d1, d2) = (b.duel(&f1, &f2), b.duel(&f2, &f1));
+
+            // A tied runoff is won by the best candidate of the score round
+            let runoff = if d1 == d2 && scores[&f1] == scores[&f2] {
+                tie = true;
+                vec![vec![f1, f2]]
+            } else {
+                tie |= d1 == d2;
+                let victory = if d1 > d2 || (d1 == d2 && scores[&f1] > scores[&f2]) {
+                    PairwiseVictory {
+                        winner: f1,
+                        loser: f2,
+                        winning_votes: d1,
+                        losing_votes: d2,
+                    }
+                } else {
+                    PairwiseVictory {
+                        winner: f2,
+                        loser: f1,
+                        winning_votes: d2,
+                        losing_votes: d1,
+                    }
+                };
+
+                steps.push(CountStep::Duel(victory.clone()));
+                let runoff = vec![vec![victory.winner.clone()], vec![victory.loser.clone()]];
+                details = Some(ResultDetails::Runoff(victory));
+                runoff
+            };
+            ranking.splice(0..ranks, runoff);
+        }
+
+        let result = ElectionResult::new(Self::LONG_NAME, ranking, scores, self.0.get_count())
+            .with_explanation(steps)
+            .with_tie(tie)
+            .break_ties(tie_break, &[]);
+        match details {
+            Some(d) => result.with_details(d),
+            None => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(s: &mut StarVoting, ballots: &[(usize, [u8; 3])]) {
+        for (n, [a, b, c]) in ballots {
+            for _ in 0..*n {
+                s.vote(SingleBallot::Scored(HashMap::from([
+                    ("A".to_string(), *a),
+                    ("B".to_string(), *b),
+                    ("C".to_string(), *c),
+                ])))
+                .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn star_voting() {
+        let mut s = StarVoting::new(&["A", "B", "C"]);
+
+        // A has the highest total but B is preferred to A by a majority
+        vote(&mut s, &[(2, [5, 3, 0]), (4, [0, 1, 2]), (1, [5, 0, 1])]);
+
+        let result = s.result();
+        assert_eq!(
+            &vec![
+                vec!["B".to_string()],
+                vec!["A".to_string()],
+                vec!["C".to_string()]
+            ],
+            result.get_ranking()
+        );
+        assert_eq!(Some(15.), result.get_score("A"));
+        match result.get_details() {
+            Some(ResultDetails::Runoff(v)) => {
+                assert_eq!(
+                    ("B", 4, 3),
+                    (v.winner.as_str(), v.winning_votes, v.losing_votes)
+                )
+            }
+            _ => panic!("missing runoff details"),
+        }
+    }
+
+    #[test]
+    fn tied_score_runoff() {
+        let mut s = StarVoting::new(&["A", "B", "C"]);
+
+        // A and B have the same total score, the runoff decides between them
+        vote(&mut s, &[(2, [4, 3, 0]), (1, [0, 2, 1])]);
+
+        let result = s.result();
+        assert_eq!(Some(8.), result.get_score("A"));
+        assert_eq!(Some(8.), result.get_score("B"));
+        assert_eq!(vec!["A".to_string()], result.get_winners());
+        assert!(!result.is_tie());
+        assert!(matches!(
+            result.get_details(),
+            Some(ResultDetails::Runoff(v)) if v.winning_votes == 2 && v.losing_votes == 1
+        ));
+
+        // Three candidates for two places are only separated by the tie-breaking policy
+        let mut s = StarVoting::new(&["A", "B", "C"]);
+        vote(&mut s, &[(1, [5, 0, 0]), (1, [0, 5, 0]), (1, [0, 0, 5])]);
+        let result = s.result();
+        assert!(result.is_tie());
+        assert!(result.get_details().is_none());
+    }
+}
@@ -0,0 +1,217 @@
+use rand::{SeedableRng, prelude::SliceRandom, rngs::Xoshiro256PlusPlus};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::voting_system::result::rank_by_scores;
+
+/// Policy used to break ties between candidates
+///
+/// Here an exemple :
+/// ```rust
+/// use libvotally::voting_system::TieBreak;
+///
+/// let t: TieBreak = "priority:C,A".parse().unwrap();
+///
+/// assert_eq!(
+///     vec![vec!["C".to_string()], vec!["A".to_string()], vec!["B".to_string()]],
+///     t.break_tie(&["A".to_string(), "B".to_string(), "C".to_string()], &[])
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TieBreak {
+    /// Keep the tie in the result
+    #[default]
+    DeclareTie,
+    /// Shuffle tied candidates with a random generator initialised with this seed
+    Random(u64),
+    /// Favour the candidates appearing first in this list
+    Priority(Vec<String>),
+    /// Favour the candidates with the best scores in the earlier rounds
+    EarlierRound,
+}
+
+impl TieBreak {
+    /// Create a random tie-breaking policy with a new seed
+    pub fn random() -> Self {
+        TieBreak::Random(rand::random())
+    }
+
+    /// Order tied candidates from best to worst
+    /// Candidates that can't be separated stay together.
+    /// `earlier_rounds` contains the scores of previous rounds, from the first one to the last one.
+    pub fn break_tie(
+        &self,
+        tied: &[String],
+        earlier_rounds: &[HashMap<String, f64>],
+    ) -> Vec<Vec<String>> {
+        let mut tied = tied.to_vec();
+        tied.sort();
+
+        match self {
+            TieBreak::DeclareTie => vec![tied],
+            TieBreak::Random(seed) => {
+                let mut rng = Xoshiro256PlusPlus::seed_from_u64(*seed);
+                tied.shuffle(&mut rng);
+                tied.into_iter().map(|c| vec![c]).collect()
+            }
+            TieBreak::Priority(priority) => {
+                let (mut listed, unlisted): (Vec<String>, Vec<String>) =
+                    tied.into_iter().partition(|c| priority.contains(c));
+                listed.sort_by_key(|c| priority.iter().position(|p| p == c));
+
+                let mut ranking: Vec<Vec<String>> = listed.into_iter().map(|c| vec![c]).collect();
+                if !unlisted.is_empty() {
+                    ranking.push(unlisted);
+                }
+                ranking
+            }
+            TieBreak::EarlierRound => match earlier_rounds.split_last() {
+                None => vec![tied],
+                Some((last, previous)) => {
+                    let scores = tied
+                        .iter()
+                        .map(|c| (c.to_owned(), last.get(c).copied().unwrap_or(0.)))
+                        .collect();
+
+                    rank_by_scores(&scores)
+                        .into_iter()
+                        .flat_map(|rank| {
+                            if rank.len() > 1 {
+                                self.break_tie(&rank, previous)
+                            } else {
+                                vec![rank]
+                            }
+                        })
+                        .collect()
+                }
+            },
+        }
+    }
+
+    /// Order tied candidates from best to worst and keep only the best ones
+    pub fn best(&self, tied: &[String], earlier_rounds: &[HashMap<String, f64>]) -> Vec<String> {
+        self.break_tie(tied, earlier_rounds)
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+    }
+
+    /// Order tied candidates from best to worst and keep only the worst ones
+    pub fn worst(&self, tied: &[String], earlier_rounds: &[HashMap<String, f64>]) -> Vec<String> {
+        self.break_tie(tied, earlier_rounds)
+            .into_iter()
+            .last()
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for TieBreak {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TieBreak::DeclareTie => write!(f, "declare"),
+            TieBreak::Random(seed) => write!(f, "random:{}", seed),
+            TieBreak::Priority(priority) => write!(f, "priority:{}", priority.join(",")),
+            TieBreak::EarlierRound => write!(f, "earlier-round"),
+        }
+    }
+}
+
+impl FromStr for TieBreak {
+    type Err = UnknownTieBreak;
+
+    /// Parse a policy among `declare`, `random`, `random:<seed>`,
+    /// `priority:<choice>,<choice>...` and `earlier-round`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "declare" => Ok(TieBreak::DeclareTie),
+            None if s == "random" => Ok(TieBreak::random()),
+            None if s == "earlier-round" => Ok(TieBreak::EarlierRound),
+            Some(("random", seed)) => seed
+                .trim()
+                .parse()
+                .map(TieBreak::Random)
+                .map_err(|_| UnknownTieBreak(s.to_string())),
+            Some(("priority", priority)) => Ok(TieBreak::Priority(
+                priority.split(',').map(|c| c.trim().to_owned()).collect(),
+            )),
+            _ => Err(UnknownTieBreak(s.to_string())),
+        }
+    }
+}
+
+/// Error for unknown tie-breaking policy
+#[derive(Debug)]
+pub struct UnknownTieBreak(String);
+
+impl fmt::Display for UnknownTieBreak {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown tie-breaking policy: {}", self.0)
+    }
+}
+
+impl Error for UnknownTieBreak {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tied() -> Vec<String> {
+        vec!["A".to_string(), "B".to_string(), "C".to_string()]
+    }
+
+    #[test]
+    fn declare_tie() {
+        assert_eq!(vec![tied()], TieBreak::DeclareTie.break_tie(&tied(), &[]));
+    }
+
+    #[test]
+    fn random_is_reproducible() {
+        let r = TieBreak::Random(42);
+
+        assert_eq!(r.break_tie(&tied(), &[]), r.break_tie(&tied(), &[]));
+        assert_eq!(3, r.break_tie(&tied(), &[]).len());
+    }
+
+    #[test]
+    fn earlier_round() {
+        let rounds = vec![
+            HashMap::from([
+                ("A".to_string(), 1.),
+                ("B".to_string(), 3.),
+                ("C".to_string(), 2.),
+            ]),
+            HashMap::from([
+                ("A".to_string(), 4.),
+                ("B".to_string(), 2.),
+                ("C".to_string(), 4.),
+            ]),
+        ];
+
+        assert_eq!(
+            vec![
+                vec!["C".to_string()],
+                vec!["A".to_string()],
+                vec!["B".to_string()]
+            ],
+            TieBreak::EarlierRound.break_tie(&tied(), &rounds)
+        );
+        assert_eq!(vec![tied()], TieBreak::EarlierRound.break_tie(&tied(), &[]));
+    }
+
+    #[test]
+    fn parse_tie_break() {
+        assert_eq!(Ok(TieBreak::Random(7)), "random:7".parse().map_err(|_| ()));
+        assert_eq!(
+            Ok(TieBreak::Priority(vec!["B".to_string(), "A".to_string()])),
+            "priority:B, A".parse().map_err(|_| ())
+        );
+        assert!("coin".parse::<TieBreak>().is_err());
+        assert_eq!(
+            "earlier-round",
+            "earlier-round".parse::<TieBreak>().unwrap().to_string()
+        );
+    }
+}
@@ -1,12 +1,18 @@
-use libvotally::voting_system::{BallotForm, SingleBallot};
-use tokio::io::{self, AsyncBufReadExt, BufReader, stdin};
+use std::collections::HashMap;
+use std::path::Path;
 
-/// Read a vote
-pub async fn read_vote(ballot_form: &BallotForm) -> io::Result<SingleBallot> {
-    let stdin = stdin();
-    let mut stdin_reader = BufReader::new(stdin);
+use libvotally::ballot_file::BallotFileFormat;
+use libvotally::network::{ElectionDefinition, VoterRoll};
+use libvotally::voting_system::{BallotForm, MinimalVotingSystemInfo, RawBallots, SingleBallot};
+use tokio::io::{self, AsyncBufRead, AsyncBufReadExt};
 
-    match ballot_form {
+/// Read a vote from the answers of the voter
+/// Every answer goes through the same reader, which may hold lines already typed
+pub async fn read_vote<R: AsyncBufRead + Unpin>(
+    stdin_reader: &mut R,
+    info: &MinimalVotingSystemInfo,
+) -> io::Result<SingleBallot> {
+    match info.get_ballot_form() {
         BallotForm::Uninominal => {
             let mut buffer = String::new();
 
@@ -31,10 +37,168 @@ pub async fn read_vote(ballot_form: &BallotForm) -> io::Result<SingleBallot> {
             println!(
                 "Enter your choices separated with comma in order of your preference (for best to worse):"
             );
+            if info.get_parameters().get_partial_ranking() {
+                println!(
+                    "You can leave out choices and separate equally ranked choices with =, like A, B = C"
+                );
+            }
             stdin_reader.read_line(&mut buffer).await?;
 
-            let ballot = buffer.split(',').map(|s| s.trim().to_owned()).collect();
-            Ok(SingleBallot::Ranked(ballot))
+            if buffer.contains('=') {
+                let ballot = buffer
+                    .split(',')
+                    .map(|group| group.split('=').map(|s| s.trim().to_owned()).collect())
+                    .collect();
+                Ok(SingleBallot::RankedWithTies(ballot))
+            } else {
+                let ballot = buffer
+                    .split(',')
+                    .map(|s| s.trim().to_owned())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+                Ok(SingleBallot::Ranked(ballot))
+            }
+        }
+        BallotForm::Scored => {
+            let range = info.get_parameters().get_score_range();
+            let mut ballot = HashMap::new();
+
+            println!(
+                "Enter a score between {} and {} for each choice:",
+                range.get_min(),
+                range.get_max()
+            );
+            for c in info.get_choices() {
+                loop {
+                    let mut buffer = String::new();
+
+                    println!("{}:", c);
+                    stdin_reader.read_line(&mut buffer).await?;
+
+                    match buffer.trim().parse() {
+                        Ok(score) if range.contains(score) => {
+                            ballot.insert(c, score);
+                            break;
+                        }
+                        _ => println!("Invalid score"),
+                    }
+                }
+            }
+
+            Ok(SingleBallot::Scored(ballot))
+        }
+        BallotForm::Graded => {
+            let grades = info.get_parameters().get_grades();
+            let mut ballot = HashMap::new();
+
+            println!("Enter a grade, by its name or its number, for each choice:");
+            for (i, g) in grades.iter().enumerate() {
+                println!("{}. {}", i + 1, g);
+            }
+            for c in info.get_choices() {
+                loop {
+                    let mut buffer = String::new();
+
+                    println!("{}:", c);
+                    stdin_reader.read_line(&mut buffer).await?;
+
+                    let answer = buffer.trim();
+                    let grade = match answer.parse::<usize>() {
+                        Ok(n) => n.checked_sub(1).filter(|g| *g < grades.len()),
+                        Err(_) => grades.iter().position(|g| g.eq_ignore_ascii_case(answer)),
+                    };
+                    match grade {
+                        Some(grade) => {
+                            ballot.insert(c, grade as u8);
+                            break;
+                        }
+                        None => println!("Invalid grade"),
+                    }
+                }
+            }
+
+            Ok(SingleBallot::Graded(ballot))
+        }
+    }
+}
+
+/// Read ballots from a file containing one ballot per line, written in RON
+pub fn read_ballots(path: &Path) -> io::Result<Vec<SingleBallot>> {
+    read_numbered_ballots(path)?
+        .into_iter()
+        .map(|(line, ballot)| {
+            ballot.map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", line, e))
+            })
+        })
+        .collect()
+}
+
+/// Read ballots in the format given by the extension of the file,
+/// or one ballot per line written in RON with the given choices
+pub fn read_ballot_file(path: &Path, choices: &[String]) -> io::Result<RawBallots> {
+    match BallotFileFormat::from_path(path) {
+        Some(format) => format
+            .read(&std::fs::read_to_string(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        None => {
+            let choices: Vec<&str> = choices.iter().map(|c| c as &str).collect();
+            Ok(RawBallots::from_ballots(&choices, read_ballots(path)?))
+        }
+    }
+}
+
+/// Write ballots in the format given by the extension of the file
+pub fn write_ballot_file(path: &Path, ballots: &RawBallots) -> io::Result<()> {
+    let format = BallotFileFormat::from_path(path).ok_or(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "unknown ballot file format",
+    ))?;
+    let output = format
+        .write(ballots)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    std::fs::write(path, output)
+}
+
+/// Read a file containing one ballot per line, written in RON.
+/// Give the number of each line with its ballot or the reason it can't be read
+pub fn read_numbered_ballots(
+    path: &Path,
+) -> io::Result<Vec<(usize, Result<SingleBallot, String>)>> {
+    Ok(std::fs::read_to_string(path)?
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(n, l)| (n + 1, ron::de::from_str(l).map_err(|e| e.to_string())))
+        .collect())
+}
+
+/// Read a voter roll with one `<name>,<weight>,<token>` per line
+pub fn read_voter_roll(path: &Path) -> io::Result<VoterRoll> {
+    std::fs::read_to_string(path)?
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Read and validate an election definition written in RON
+pub fn read_election_definition(path: &Path) -> io::Result<ElectionDefinition> {
+    std::fs::read_to_string(path)?
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Print the title, the description and the candidates of an election
+pub fn print_election(definition: &ElectionDefinition) {
+    println!("{}", definition.get_title());
+    if !definition.get_description().is_empty() {
+        println!("{}", definition.get_description());
+    }
+    for c in definition.get_candidates() {
+        if c.get_description().is_empty() {
+            println!("- {}", c.get_name());
+        } else {
+            println!("- {}: {}", c.get_name(), c.get_description());
         }
     }
 }
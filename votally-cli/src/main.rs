@@ -1,92 +1,434 @@
+use std::net::IpAddr;
+use std::path::PathBuf;
 use std::process;
+use std::time::Duration;
 
 use tokio::io::{self, AsyncBufReadExt};
 
-use clap::Parser;
+use clap::builder::{PossibleValue, PossibleValuesParser};
+use clap::{Parser, Subcommand};
 
-use libvotally::network::{VotallyClient, VotallyServer};
-use libvotally::voting_system::UnknownVotingSystem;
+use libvotally::network::{
+    Candidate, ElectionDefinition, Error, VotallyClient, VotallyServer, VoterCredentials,
+    split_host_port,
+};
+use libvotally::voting_system::{
+    ScoreRange, TieBreak, UnknownVotingSystem, VotingParameters, check_seats, compare,
+    find_voting_system, registry,
+};
 
 use votally_cli::*;
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Create a server for holding a vote
     #[arg(short, long, action = clap::ArgAction::SetTrue)]
     server: bool,
 
-    /// Name of the used voting system among approval, plurality, borda, black
-    #[arg(short, long, default_value = "approval")]
+    /// Name of the used voting system
+    #[arg(short, long, default_value = "approval", value_parser = voting_system_parser())]
     voting_system: String,
 
+    /// Tie-breaking policy among declare, random, random:<seed>,
+    /// priority:<choice>,<choice>... and earlier-round
+    #[arg(short, long, default_value = "declare", global = true)]
+    tie_break: TieBreak,
+
+    /// Number of seats to fill, for multi-winner voting systems like stv
+    #[arg(long, default_value_t = 1, global = true)]
+    seats: usize,
+
+    /// Range of scores for scored ballots, written <min>-<max>
+    #[arg(long, default_value = "0-5", global = true)]
+    score_range: ScoreRange,
+
+    /// Grades for graded ballots from the best to the worst, separated with comma
+    #[arg(
+        long,
+        value_delimiter = ',',
+        global = true,
+        default_value = "Excellent,Very good,Good,Fair,Poor,Reject"
+    )]
+    grades: Vec<String>,
+
+    /// Allow ranked ballots leaving out choices or ranking several choices equally
+    #[arg(long, action = clap::ArgAction::SetTrue, global = true)]
+    partial_ranking: bool,
+
+    /// File defining the election held by a server, written in RON,
+    /// replacing the choices, the voting system and its parameters
+    #[arg(
+        short,
+        long,
+        conflicts_with_all = [
+            "choices",
+            "voter_roll",
+            "voting_system",
+            "tie_break",
+            "seats",
+            "score_range",
+            "grades",
+            "partial_ranking",
+        ]
+    )]
+    election: Option<PathBuf>,
+
+    /// File listing the voters of a server, one `<name>,<weight>,<token>` per line,
+    /// each voter carrying weight votes and proving who they are with their token
+    #[arg(long)]
+    voter_roll: Option<PathBuf>,
+
+    /// Address a server listens on, 0.0.0.0 or :: to listen on every interface,
+    /// the local IP if left out
+    #[arg(long)]
+    bind: Option<IpAddr>,
+
+    /// Port a server listens on, replacing the one of the election file
+    #[arg(short, long)]
+    port: Option<u16>,
+
+    /// Server joined by the client, written <host> or <host>:<port>,
+    /// instead of looking for servers on the local network
+    #[arg(long, conflicts_with = "server")]
+    connect: Option<String>,
+
     /// List of choices for a server
     // #[arg(short, long)]
     choices: Vec<String>,
 }
 
+#[derive(Subcommand)]
+enum Command {
+    /// Count the same ballots with every compatible voting system
+    Compare {
+        /// File containing one ballot per line, written in RON,
+        /// or a .blt, .soc, .soi, .toc, .toi, .abif or .csv file
+        ballots: PathBuf,
+
+        /// List of choices of the RON ballots
+        choices: Vec<String>,
+    },
+    /// Convert ballots to another format, chosen from the extension of the output file
+    Convert {
+        /// File containing one ballot per line, written in RON,
+        /// or a .blt, .soc, .soi, .toc, .toi, .abif or .csv file
+        input: PathBuf,
+
+        /// A .blt, .soc, .abif or .csv file
+        output: PathBuf,
+
+        /// List of choices of the RON ballots
+        choices: Vec<String>,
+    },
+    /// Count a file of ballots with one voting system, without a server
+    Tally {
+        /// Name of the used voting system
+        #[arg(short, long, value_parser = voting_system_parser())]
+        method: String,
+
+        /// File containing one ballot per line, written in RON
+        ballots: PathBuf,
+
+        /// List of choices of the ballots, found in the ballots if left out
+        choices: Vec<String>,
+    },
+}
+
+/// Accept the names of the registered voting systems
+fn voting_system_parser() -> PossibleValuesParser {
+    PossibleValuesParser::new(
+        registry()
+            .factories()
+            .map(|f| PossibleValue::new(f.name()).help(f.long_name())),
+    )
+}
+
+/// Stop on an error of the network
+fn network_error<T>(e: Error) -> T {
+    eprintln!("{}", e);
+    process::exit(1);
+}
+
+/// Exit when the answer of the user can't be read
+fn input_error<T>(e: io::Error) -> T {
+    eprintln!("Can't read the answer: {}", e);
+    process::exit(1);
+}
+
+/// Find the IP of this computer on the local network and exit if there is none
+fn local_ip() -> IpAddr {
+    local_ip_address::local_ip().unwrap_or_else(|e| {
+        eprintln!("Can't find the IP of this computer: {}", e);
+        process::exit(1);
+    })
+}
+
+/// Check the parameters of an election and exit if they are not correct
+fn check_parameters(parameters: &VotingParameters, choices: &[String]) {
+    let choices: Vec<&str> = choices.iter().map(|c| c.as_str()).collect();
+    parameters.validate(&choices).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+}
+
 #[tokio::main]
 async fn main() -> Result<(), UnknownVotingSystem> {
     let cli = Cli::parse();
+    let parameters = VotingParameters::default()
+        .with_tie_break(cli.tie_break.clone())
+        .with_seats(cli.seats)
+        .with_score_range(cli.score_range)
+        .with_grades(cli.grades.clone())
+        .with_partial_ranking(cli.partial_ranking);
+
+    match &cli.command {
+        Some(Command::Compare { ballots, choices }) => {
+            let ballots = read_ballot_file(ballots, choices).unwrap_or_else(|e| {
+                eprintln!("Can't read the ballots: {}", e);
+                process::exit(1);
+            });
+            check_parameters(&parameters, ballots.get_choices());
 
-    if cli.server {
-        if cli.choices.len() <= 1 {
-            eprintln!("There is not enough choice.");
-            process::exit(1);
+            println!("{}", compare(&ballots, &parameters));
         }
+        Some(Command::Convert {
+            input,
+            output,
+            choices,
+        }) => {
+            let ballots = read_ballot_file(input, choices).unwrap_or_else(|e| {
+                eprintln!("Can't read the ballots: {}", e);
+                process::exit(1);
+            });
+            write_ballot_file(output, &ballots).unwrap_or_else(|e| {
+                eprintln!("Can't write the ballots: {}", e);
+                process::exit(1);
+            });
+        }
+        Some(Command::Tally {
+            method,
+            ballots,
+            choices,
+        }) => {
+            let ballots = read_numbered_ballots(ballots).unwrap_or_else(|e| {
+                eprintln!("Can't read the ballots: {}", e);
+                process::exit(1);
+            });
 
-        let server_address = local_ip_address::local_ip().unwrap();
-        println!("Server IP: {}", server_address);
-        let mut server = VotallyServer::build(
-            server_address.to_string(),
-            cli.voting_system,
-            &cli.choices.iter().map(|c| c as &str).collect(),
-        )
-        .await?;
+            let choices: Vec<String> = if choices.is_empty() {
+                let mut found: Vec<String> = ballots
+                    .iter()
+                    .filter_map(|(_, b)| b.as_ref().ok())
+                    .flat_map(|b| b.choices())
+                    .cloned()
+                    .collect();
+                found.sort();
+                found.dedup();
+                found
+            } else {
+                choices.clone()
+            };
+            check_parameters(&parameters, &choices);
 
-        press_enter("start ballot");
+            let choices: Vec<&str> = choices.iter().map(|c| c as &str).collect();
+            let mut election = find_voting_system(method, &choices, &parameters)?;
+            check_seats(method, &parameters).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                process::exit(1);
+            });
 
-        server.start_ballot().await.unwrap();
+            // The election checks every ballot before counting it
+            let mut rejected = 0;
+            for (line, ballot) in ballots {
+                if let Err(e) = ballot.and_then(|b| election.vote(b).map_err(|e| e.to_string())) {
+                    eprintln!("Line {}: {}", line, e);
+                    rejected += 1;
+                }
+            }
+            if rejected > 0 {
+                eprintln!("{} ballots rejected", rejected);
+            }
 
-        press_enter("end vote");
+            println!("{}", election.result());
+        }
+        None if cli.server => {
+            let definition = match &cli.election {
+                Some(path) => read_election_definition(path).unwrap_or_else(|e| {
+                    eprintln!("Can't read the election: {}", e);
+                    process::exit(1);
+                }),
+                None => {
+                    let candidates = cli.choices.iter().map(|c| Candidate::new(c, "")).collect();
+                    let definition =
+                        ElectionDefinition::new("Votally election", candidates, &cli.voting_system)
+                            .with_parameters(parameters);
 
-        server.end_poll().await;
+                    match &cli.voter_roll {
+                        Some(path) => {
+                            definition.with_voter_roll(read_voter_roll(path).unwrap_or_else(|e| {
+                                eprintln!("Can't read the voter roll: {}", e);
+                                process::exit(1);
+                            }))
+                        }
+                        None => definition,
+                    }
+                }
+            };
+            definition.validate().unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                process::exit(1);
+            });
+            print_election(&definition);
 
-        server.calculate_result().await;
+            let bind = cli.bind.unwrap_or_else(local_ip);
+            let port = cli.port.unwrap_or(definition.get_port());
+            let mut server = VotallyServer::from_definition((bind, port), &definition)
+                .await
+                .unwrap_or_else(network_error);
 
-        println!("Winner: {}", server.result());
-    } else {
-        let mut server_address = String::new();
+            // Voters need an IP they can reach when the server listens on every interface
+            if bind.is_unspecified() {
+                println!("Server IP: {}", local_ip());
+            } else {
+                println!("Server IP: {}", bind);
+            }
+            if port != VotallyServer::PORT {
+                println!("Server port: {}", port);
+            }
+            if !server.is_discoverable() {
+                println!(
+                    "Voters have to enter the server IP, the server can't answer the ones looking for it"
+                );
+            }
 
-        {
-            let mut stdin_reader = io::BufReader::new(io::stdin());
+            if let Some(voter_roll) = definition.get_voter_roll() {
+                println!("{} voters on the roll", voter_roll.len());
+            }
 
-            println!("Enter your server IP:");
-            stdin_reader.read_line(&mut server_address).await.unwrap();
-            server_address = server_address.trim().to_owned();
-        }
+            press_enter("start ballot");
+
+            server.start_ballot().await.unwrap_or_else(network_error);
 
-        let mut client = VotallyClient::new(server_address).await;
-        println!("Client started !");
+            press_enter("end vote");
 
-        let info = client.get_info().await;
-        println!("{}", info);
-        let ballot_form = info.get_ballot_form();
+            server.end_poll().await;
 
-        let mut ballot = read_vote(&ballot_form).await.unwrap();
-        while info
-            .check_ballot(&ballot)
-            .inspect_err(|e| println!("{e}"))
-            .is_err()
-        {
-            ballot = read_vote(&ballot_form).await.unwrap();
+            server
+                .calculate_result()
+                .await
+                .unwrap_or_else(network_error);
+
+            if let Some(result) = server.result() {
+                println!("{}", result);
+            }
         }
-        println!("Valid ballot");
+        None => {
+            // Lines typed ahead stay in the buffer, so every prompt shares this reader
+            let mut stdin_reader = io::BufReader::new(io::stdin());
+
+            let server_address = match &cli.connect {
+                Some(address) => address.to_owned(),
+                None => {
+                    // Voters can still type the address if broadcasting is not allowed on the network
+                    let servers = VotallyClient::discover(Duration::from_secs(1))
+                        .await
+                        .unwrap_or_else(|e| {
+                            eprintln!("Can't look for servers: {}", e);
+                            Vec::new()
+                        });
+
+                    if servers.is_empty() {
+                        println!("Enter your server IP (and :<port> if it isn't the default one):");
+                    } else {
+                        println!("Servers found on the local network:");
+                        for (i, server) in servers.iter().enumerate() {
+                            println!("{}. {}", i + 1, server);
+                        }
+                        println!(
+                            "Enter the number of your server, or its IP (and :<port> if it isn't the default one):"
+                        );
+                    }
+
+                    let mut answer = String::new();
+                    stdin_reader
+                        .read_line(&mut answer)
+                        .await
+                        .unwrap_or_else(input_error);
+                    match answer.trim().parse::<usize>() {
+                        Ok(n) if 1 <= n && n <= servers.len() => {
+                            servers[n - 1].get_address().to_string()
+                        }
+                        _ => answer.trim().to_owned(),
+                    }
+                }
+            };
+
+            let Some(address) = split_host_port(&server_address, VotallyServer::PORT) else {
+                eprintln!("Invalid server address: {}", server_address);
+                process::exit(1);
+            };
+            let mut client = VotallyClient::new((address.0.as_str(), address.1))
+                .await
+                .unwrap_or_else(network_error);
+            println!("Client started !");
 
-        client.send_vote(&ballot).await;
-        println!("Vote cast !");
+            let election = client.get_info().await.unwrap_or_else(network_error);
+            println!("{}", election);
 
-        println!("Winner: {}", client.result().await);
+            if election.has_voter_roll() {
+                let mut voter = String::new();
+                let mut token = String::new();
+
+                println!("Enter your name on the voter roll:");
+                stdin_reader
+                    .read_line(&mut voter)
+                    .await
+                    .unwrap_or_else(input_error);
+                println!("Enter your voter token:");
+                stdin_reader
+                    .read_line(&mut token)
+                    .await
+                    .unwrap_or_else(input_error);
+
+                client = client.with_voter(VoterCredentials::new(voter.trim(), token.trim()));
+            }
+            let info = election.get_voting_system();
+            loop {
+                let mut ballot = read_vote(&mut stdin_reader, info)
+                    .await
+                    .unwrap_or_else(input_error);
+                while info
+                    .check_ballot(&ballot)
+                    .inspect_err(|e| println!("{e}"))
+                    .is_err()
+                {
+                    ballot = read_vote(&mut stdin_reader, info)
+                        .await
+                        .unwrap_or_else(input_error);
+                }
+                println!("Valid ballot");
+
+                // The server can still reject the ballot, then another one can be cast
+                match client.send_vote(&ballot).await {
+                    Ok(()) => {
+                        println!("Vote cast !");
+                        break;
+                    }
+                    Err(e @ Error::BallotRejected(_)) => println!("{}", e),
+                    Err(e) => network_error(e),
+                }
+                if client.is_voting_closed() {
+                    break;
+                }
+            }
+
+            println!("{}", client.result().await.unwrap_or_else(network_error));
+        }
     }
 
     Ok(())